@@ -0,0 +1,60 @@
+use crate::{memop_err, MemOpResult};
+
+use iced_x86::{Decoder, DecoderError, DecoderOptions, Formatter, Instruction, NasmFormatter};
+
+/// Bitness of the code hooks are built for, matching the `.arch x86` used by `newmem_jmp`.
+pub const HOOK_BITNESS: u32 = 32;
+
+/// Decodes the instructions in a byte slice.
+///
+/// A trailing instruction cut off by the end of the slice (e.g. a pattern ending mid-instruction) is dropped.
+///
+/// # Arguments
+/// * `bytes`: Bytes to decode
+/// * `ip`: Address the bytes are located at, used to resolve relative operands
+/// * `bitness`: 16, 32 or 64
+/// # Returns
+/// * `MemOpResult<Vec<Instruction>>`: The decoded instructions, errors on an invalid instruction
+pub fn decode(bytes: &[u8], ip: usize, bitness: u32) -> MemOpResult<Vec<Instruction>> {
+    let mut decoder = Decoder::try_with_ip(bitness, bytes, ip as u64, DecoderOptions::NONE)
+        .map_err(|e| memop_err!("Could not create decoder: {e}"))?;
+
+    let mut instructions = Vec::new();
+    while decoder.can_decode() {
+        let instruction = decoder.decode();
+
+        if instruction.is_invalid() {
+            if decoder.last_error() == DecoderError::NoMoreBytes {
+                break;
+            }
+
+            return Err(memop_err!(
+                "Invalid instruction at \"{:#X}\".",
+                instruction.ip()
+            ));
+        }
+
+        instructions.push(instruction);
+    }
+
+    Ok(instructions)
+}
+
+/// Formats decoded instructions as NASM-style text, e.g. `mov eax,[ebp+8]`.
+pub fn format_instructions(instructions: &[Instruction]) -> Vec<String> {
+    let mut formatter = NasmFormatter::new();
+
+    instructions
+        .iter()
+        .map(|instruction| {
+            let mut output = String::new();
+            formatter.format(instruction, &mut output);
+            output
+        })
+        .collect()
+}
+
+/// Decodes and formats the instructions in a byte slice. See `decode()`.
+pub fn disassemble(bytes: &[u8], ip: usize, bitness: u32) -> MemOpResult<Vec<String>> {
+    Ok(format_instructions(&decode(bytes, ip, bitness)?))
+}
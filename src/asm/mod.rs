@@ -1,7 +1,15 @@
+#[cfg(feature = "disassembly")]
+pub mod disasm;
+
 use crate::hooks::*;
+use crate::memory::{read::read_bytes, write::write_bytes};
+use crate::process::target::is_process_32bit;
+use crate::process::SafeHandle;
+use crate::MemOpResult;
 
 use anyhow::{anyhow, Result};
 use dynasmrt::{dynasm, x86::X86Relocation, Assembler, DynasmApi};
+use std::time::Duration;
 
 /// Compiles a dynasmrt Assembler, and provides anyhow error propogation.
 ///
@@ -108,14 +116,33 @@ fn apply_nops(
 /// # Returns
 /// * `anyhow::Result<dynasm::Assembler<dynasmrt::x86::X86Relocation>>`: Anyhow result containing the Assembler object
 pub fn newmem_jmp(hook: &HookData) -> Result<Assembler<X86Relocation>> {
+    newmem_inject(hook, InjectKind::Jmp)
+}
+
+/// Sets up a `dynasm::Assembler` to a hook's newmem address, with a `jmp` or a `call` depending on `kind`.
+///
+/// Both are 5 bytes (`E9`/`E8` rel32), so the displacement is relative to the end of the inject point's instruction either way.
+///
+/// # Arguments
+/// * `hook`: Hook runtime data
+/// * `kind`: Instruction used at the inject point
+/// # Returns
+/// * `anyhow::Result<dynasm::Assembler<dynasmrt::x86::X86Relocation>>`: Anyhow result containing the Assembler object
+pub fn newmem_inject(hook: &HookData, kind: InjectKind) -> Result<Assembler<X86Relocation>> {
     let mut ops: Assembler<X86Relocation> = Assembler::new()?;
     let newmem = hook.hook_mem.addr as i32;
-    let newmem_rel_jmp = newmem - (hook.get_addr()? as i32 + 5);
+    let newmem_rel = newmem - (hook.get_addr()? as i32 + 5);
 
-    dynasm!(ops
-        ; .arch x86
-        ; jmp newmem_rel_jmp
-    );
+    match kind {
+        InjectKind::Jmp => dynasm!(ops
+            ; .arch x86
+            ; jmp newmem_rel
+        ),
+        InjectKind::Call => dynasm!(ops
+            ; .arch x86
+            ; call newmem_rel
+        ),
+    }
 
     Ok(ops)
 }
@@ -152,3 +179,100 @@ pub fn end_jmp(
 
     Ok(())
 }
+
+/// Builds the bytes of a jump placed at `from` that lands on `to`.
+///
+/// A 5 byte `jmp rel32` is used whenever the displacement fits, which in 32-bit code it always does
+/// as it wraps around the address space. Far targets in 64-bit code get a 14 byte absolute `jmp [rip+0]`
+/// followed by the 64-bit target; in 32-bit code that encoding is `jmp [0]`, so it is never emitted there.
+///
+/// # Arguments
+/// * `from`: The address the jump will be written to
+/// * `to`: The address to jump to
+/// * `is_32bit`: Whether the jump is executed as 32-bit code
+/// # Returns
+/// * `Vec<u8>`: The encoded jump instruction
+pub fn build_jump(from: usize, to: usize, is_32bit: bool) -> Vec<u8> {
+    if is_32bit {
+        let rel32 = (to as u32).wrapping_sub((from as u32).wrapping_add(5));
+        let mut bytes = vec![0xE9];
+        bytes.extend_from_slice(&rel32.to_le_bytes());
+        return bytes;
+    }
+
+    let rel = (to as i64).wrapping_sub(from as i64 + 5);
+
+    match i32::try_from(rel) {
+        Ok(rel32) => {
+            let mut bytes = vec![0xE9];
+            bytes.extend_from_slice(&rel32.to_le_bytes());
+            bytes
+        }
+        Err(_) => {
+            let mut bytes = vec![0xFF, 0x25, 0x00, 0x00, 0x00, 0x00];
+            bytes.extend_from_slice(&(to as u64).to_le_bytes());
+            bytes
+        }
+    }
+}
+
+/// Writes a jump at an arbitrary address, outside of the `Hook` machinery.
+///
+/// The jump is encoded for the target's bitness, see `build_jump()`.
+///
+/// # Arguments
+/// * `handle`: Handle to the target process
+/// * `from_addr`: The address the jump will be written to
+/// * `to_addr`: The address to jump to
+/// * `timeout`: Optional timeout for the read/write operations
+/// # Returns
+/// * `MemOpResult<Vec<u8>>`: The original bytes overwritten by the jump, for later restoration
+pub fn write_jump(
+    handle: &SafeHandle,
+    from_addr: usize,
+    to_addr: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<u8>> {
+    let jump = build_jump(from_addr, to_addr, is_process_32bit(handle)?);
+    let original = read_bytes(handle, from_addr, jump.len(), timeout)?;
+
+    write_bytes(handle, from_addr, &jump, timeout)?;
+
+    Ok(original)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_jump;
+
+    fn rel32_target(from: usize, jump: &[u8]) -> usize {
+        let rel = i32::from_le_bytes(jump[1..5].try_into().unwrap());
+        (from as i64 + 5 + rel as i64) as usize
+    }
+
+    #[test]
+    fn build_jump_rel32_resolves_to_target() {
+        let jump = build_jump(0x40_1000, 0x40_0000, false);
+
+        assert_eq!(jump[0], 0xE9);
+        assert_eq!(jump.len(), 5);
+        assert_eq!(rel32_target(0x40_1000, &jump), 0x40_0000);
+    }
+
+    #[test]
+    fn build_jump_far_64bit_is_absolute() {
+        let jump = build_jump(0x1000, 0x7FF0_0000_0000, false);
+
+        assert_eq!(jump[..6], [0xFF, 0x25, 0, 0, 0, 0]);
+        assert_eq!(u64::from_le_bytes(jump[6..].try_into().unwrap()), 0x7FF0_0000_0000);
+    }
+
+    #[test]
+    fn build_jump_32bit_wraps_instead_of_going_absolute() {
+        let jump = build_jump(0xF000_0000, 0x1000, true);
+        let rel = u32::from_le_bytes(jump[1..5].try_into().unwrap());
+
+        assert_eq!(jump[0], 0xE9);
+        assert_eq!(0xF000_0005u32.wrapping_add(rel), 0x1000);
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(feature = "async")]
 #[macro_export]
-/// Clones a SafeHandle and passes it into a smol::unblock closure, awaited.
+/// Clones a SafeHandle and passes it into a `zhol::async_ext::unblock()` closure, awaited.
 /// Effectively, this lets you run synchronous operations that use WinAPI handles asynchronously.
 macro_rules! await_memop {
     (
@@ -9,7 +9,7 @@ macro_rules! await_memop {
     ) => {{
         let handle_clone = $handle.clone();
 
-        smol::unblock(move || -> MemOpResult<_> { $body(handle_clone) }).await
+        $crate::async_ext::unblock(move || -> MemOpResult<_> { $body(handle_clone) }).await
     }};
 }
 
@@ -22,6 +22,293 @@ macro_rules! async_memop {
         $body:expr
     ) => {{
         let handle_clone = $handle.clone();
-        smol::unblock(move || -> MemOpResult<_> { $body(handle_clone) })
+        $crate::async_ext::unblock(move || -> MemOpResult<_> { $body(handle_clone) })
     }};
-}
\ No newline at end of file
+}
+
+/// Limit set through `set_blocking_pool_size()`, `None` while unlimited.
+static BLOCKING_LIMIT: parking_lot::RwLock<Option<std::sync::Arc<smol::lock::Semaphore>>> =
+    parking_lot::const_rwlock(None);
+
+/// Bounds how many blocking operations `await_memop!`/`async_memop!` (and the other async functions) run at once.
+///
+/// `None` removes the limit, leaving concurrency to smol's blocking pool. Operations already waiting
+/// or running keep the limit they started with.
+///
+/// # Example
+/// ```rust,norun
+/// // At most two ReadProcessMemory calls in flight
+/// zhol::async_ext::set_blocking_pool_size(Some(2));
+/// ```
+pub fn set_blocking_pool_size(size: Option<usize>) {
+    *BLOCKING_LIMIT.write() = size.map(|size| std::sync::Arc::new(smol::lock::Semaphore::new(size)));
+}
+
+/// Runs a blocking closure on smol's blocking pool, within the limit set by `set_blocking_pool_size()`.
+///
+/// Like `smol::unblock()`, the closure is started right away rather than once the task is first polled.
+/// Under a limit, it waits for its permit on the blocking pool and holds it until it returns.
+pub fn unblock<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> smol::Task<T> {
+    let limit = BLOCKING_LIMIT.read().clone();
+
+    smol::unblock(move || {
+        let _permit = limit.as_deref().map(smol::lock::Semaphore::acquire_blocking);
+        f()
+    })
+}
+
+/// Amount of bytes `scan_stream()` reads and scans at a time, before emitting the matches found.
+const STREAM_SCAN_WINDOW: usize = 0x10_0000;
+
+/// Amount of matches `scan_stream()` buffers before the scan waits for the consumer to catch up.
+const STREAM_SCAN_BUFFER: usize = 0x100;
+
+/// Scans `size` bytes from `origin` for an IDA pattern on the blocking thread pool, yielding each match as it is found.
+///
+/// The region is read one window at a time, so matches show up while the scan is still running.
+/// Setting `cancel` stops the scan, also while a window waits to become readable. Dropping the stream
+/// stops it after the current window.
+/// A failed read is yielded as an error and ends the stream. A slow consumer pauses the scan
+/// once `STREAM_SCAN_BUFFER` matches are waiting, rather than letting them pile up.
+///
+/// # Returns
+/// Returns a stream of the absolute address of every match, in ascending order
+///
+/// # Example
+/// ```rust,norun
+/// use smol::stream::StreamExt;
+///
+/// let mut matches = scan_stream(&handle, "8B 45 ?? 89", origin, size, Some(cancel.clone()));
+/// while let Some(addr) = matches.next().await {
+///     ui.add_result(addr?);
+/// }
+/// ```
+pub fn scan_stream(
+    handle: &crate::process::SafeHandle,
+    pattern: impl Into<crate::process::pattern::Pattern>,
+    origin: usize,
+    size: usize,
+    cancel: Option<crate::memory::utils::CancellationToken>,
+) -> impl smol::stream::Stream<Item = crate::MemOpResult<usize>> + Send + 'static {
+    use crate::memory::utils::check_cancelled;
+    use crate::process::pattern::find_pattern_offsets;
+
+    let (sender, receiver) = smol::channel::bounded(STREAM_SCAN_BUFFER);
+    let handle = handle.clone();
+    let pattern = pattern.into().into_bytes();
+
+    smol::unblock(move || {
+        let pattern_length = pattern.len();
+        let mut start = 0;
+
+        while start < size {
+            if check_cancelled(cancel.as_ref()).is_err() || sender.is_closed() {
+                return;
+            }
+
+            // Same overlap as `find_pattern_chunked()`, so matches straddling windows are found once.
+            let len = (STREAM_SCAN_WINDOW + pattern_length.saturating_sub(1)).min(size - start);
+            let read = crate::memory::read::read_bytes_cancellable(
+                &handle,
+                origin + start,
+                len,
+                None,
+                cancel.as_ref(),
+            );
+            let bytes = match read {
+                Ok(bytes) => bytes,
+                Err(crate::MemOpError::Cancelled) => return,
+                Err(e) => {
+                    _ = sender.send_blocking(Err(e));
+                    return;
+                }
+            };
+
+            for offset in find_pattern_offsets(&bytes, &pattern) {
+                if offset < STREAM_SCAN_WINDOW && sender.send_blocking(Ok(origin + start + offset)).is_err() {
+                    return;
+                }
+            }
+
+            start += STREAM_SCAN_WINDOW;
+        }
+    })
+    .detach();
+
+    receiver
+}
+
+/// Interval `async_bootstrap()` polls the module list at while waiting for the hook's module to load.
+const BOOTSTRAP_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Attaches to a process, waits for the hook's module to load and installs the hook, all within one deadline.
+///
+/// `timeout` bounds the whole bootstrap rather than each step; every step gets what is left of it.
+/// Attaching and module polls still running on the blocking thread pool when the deadline passes are
+/// abandoned, not interrupted. Installing the hook is cancelled between its steps instead, and if it
+/// completes anyway, the hook is dropped again, which unhooks it.
+/// `cancel` is checked between steps and on every poll for the module.
+///
+/// # Arguments
+/// * `target` - The PID or executable name of the process, see `attach()`
+/// * `hook_impl` - The hook to install, its `module_name()` is waited for
+/// * `timeout` - Total time the bootstrap may take
+/// * `cancel` - Optional token aborting the bootstrap with `MemOpError::Cancelled`
+///
+/// # Returns
+/// Returns the attached target and the installed hook, or `MemOpError::TimeoutReached` naming the step the deadline passed in
+///
+/// # Example
+/// ```rust,norun
+/// let (target, hook) = async_bootstrap("game.exe", PlayerHook, Duration::from_secs(30), None).await?;
+/// ```
+pub async fn async_bootstrap(
+    target: impl Into<crate::process::target::AttachTarget>,
+    hook_impl: impl crate::hooks::HookImpl + 'static,
+    timeout: std::time::Duration,
+    cancel: Option<crate::memory::utils::CancellationToken>,
+) -> crate::MemOpResult<(crate::process::Target, std::sync::Arc<crate::hooks::Hook>)> {
+    use crate::hooks::{Hook, HookOps};
+    use crate::memory::utils::{check_cancelled, CancellationToken};
+    use crate::process::module::module_by_name;
+
+    let deadline = std::time::Instant::now() + timeout;
+    let target = target.into();
+
+    check_cancelled(cancel.as_ref())?;
+    let SendTarget(target) = within_deadline(
+        deadline,
+        timeout,
+        "attaching",
+        unblock(move || crate::process::attach(target).map(SendTarget)),
+    )
+    .await?;
+
+    let module_name = hook_impl.module_name();
+    loop {
+        check_cancelled(cancel.as_ref())?;
+
+        let handle = target.handle.clone();
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        let loaded = within_deadline(
+            deadline,
+            timeout,
+            "waiting for module",
+            unblock(move || {
+                module_by_name(&handle, module_name, true, Some(remaining)).map(|m| m.is_some())
+            }),
+        )
+        .await?;
+
+        if loaded {
+            break;
+        }
+
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        smol::Timer::after(BOOTSTRAP_POLL_INTERVAL.min(remaining)).await;
+    }
+
+    check_cancelled(cancel.as_ref())?;
+    let handle = target.handle.clone();
+    let abandoned: CancellationToken = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let step_abandoned = abandoned.clone();
+    let hooked = within_deadline(
+        deadline,
+        timeout,
+        "hooking",
+        unblock(move || {
+            let hook = Hook::new(handle, hook_impl)?;
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            hook.hook_cancellable(remaining, Some(&step_abandoned))?;
+
+            // Nobody is waiting for the hook anymore, dropping it unhooks it again.
+            check_cancelled(Some(&step_abandoned))?;
+            Ok(hook)
+        }),
+    )
+    .await;
+
+    if hooked.is_err() {
+        abandoned.store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    Ok((target, hooked?))
+}
+
+/// Carries a `Target` off the blocking thread pool.
+struct SendTarget(crate::process::Target);
+
+// The raw pointers in `Target` are a module handle and addresses in the target process, never dereferenced locally.
+unsafe impl Send for SendTarget {}
+
+/// Runs a bootstrap step, failing with `MemOpError::TimeoutReached` if `deadline` passes first.
+async fn within_deadline<T>(
+    deadline: std::time::Instant,
+    timeout: std::time::Duration,
+    step: &str,
+    future: impl std::future::Future<Output = crate::MemOpResult<T>>,
+) -> crate::MemOpResult<T> {
+    let expired = async {
+        smol::Timer::at(deadline).await;
+        Err(crate::MemOpError::TimeoutReached((
+            Some(timeout),
+            Some(anyhow::anyhow!("async_bootstrap(): {step}")),
+        )))
+    };
+
+    smol::future::or(future, expired).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn unblock_runs_one_at_a_time_in_a_pool_of_one() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let most_running = Arc::new(AtomicUsize::new(0));
+
+        set_blocking_pool_size(Some(1));
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let (running, most_running) = (running.clone(), most_running.clone());
+                unblock(move || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    most_running.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        set_blocking_pool_size(None);
+
+        smol::block_on(async {
+            for task in tasks {
+                task.await;
+            }
+        });
+
+        assert_eq!(most_running.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn unblock_starts_before_being_polled() {
+        let started = Arc::new(AtomicBool::new(false));
+
+        let task = {
+            let started = started.clone();
+            unblock(move || started.store(true, Ordering::SeqCst))
+        };
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while !started.load(Ordering::SeqCst) && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        assert!(started.load(Ordering::SeqCst));
+        smol::block_on(task);
+    }
+}
@@ -32,6 +32,10 @@ pub enum MemOpError {
         ),
     ),
     PatternNotFound,
+    /// The target process exited, confirmed after an operation on it failed
+    ProcessExited,
+    /// The operation was aborted through its cancellation token
+    Cancelled,
     /// WinAPI errors
     WinAPI((windows_result::Error, Option<anyhow::Error>)),
     /// Generic error that wraps an anyhow::Error
@@ -76,6 +80,40 @@ impl MemOpError {
         matches!(self, MemOpError::WinAPI(_))
     }
 
+    /// Returns true if this is a `ProcessExited` error
+    pub fn is_process_exited(&self) -> bool {
+        matches!(self, MemOpError::ProcessExited)
+    }
+
+    /// Returns true if this is a `Cancelled` error
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, MemOpError::Cancelled)
+    }
+
+    /// Returns true if this is a WinAPI failure that an exited process produces.
+    ///
+    /// These codes are also produced by permission problems, so the process still has to be checked.
+    pub(crate) fn is_exit_symptom(&self) -> bool {
+        const EXIT_CODES: [u32; 3] = [
+            5,   // ERROR_ACCESS_DENIED
+            6,   // ERROR_INVALID_HANDLE
+            299, // ERROR_PARTIAL_COPY
+        ];
+
+        let code = match self {
+            MemOpError::WinAPI((api_err, _)) => api_err.code().0,
+            MemOpError::Other(err) => match err.downcast_ref::<windows::core::Error>() {
+                Some(api_err) => api_err.code().0,
+                None => return false,
+            },
+            _ => return false,
+        };
+
+        EXIT_CODES
+            .iter()
+            .any(|&c| HRESULT::from_win32(c).0 == code)
+    }
+
     /// Converts this error to its underlying root cause string
     pub fn root_cause_string(&self) -> String {
         match self {
@@ -137,6 +175,8 @@ impl MemOpError {
                 }
             }
             MemOpError::PatternNotFound => format!("Pattern not found"),
+            MemOpError::ProcessExited => "Target process has exited".to_string(),
+            MemOpError::Cancelled => "Operation was cancelled".to_string(),
             MemOpError::Other(err) => format!("{:#}", err),
         }
     }
@@ -416,6 +456,14 @@ impl From<windows_result::Error> for MemOpError {
     }
 }
 
+// windows::core re-exports an older windows_result, so its errors are rebuilt from their HRESULT and message
+impl From<windows::core::Error> for MemOpError {
+    fn from(err: windows::core::Error) -> Self {
+        let api_err = windows_result::Error::new(HRESULT(err.code().0), err.message());
+        MemOpError::WinAPI((api_err, None))
+    }
+}
+
 // Create a specialized trait for windows_result::Result
 pub trait FromWindowsResult<T> {
     fn into_memop_result(self, ctx: Option<MemOpError>) -> MemOpResult<T>;
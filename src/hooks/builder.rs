@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use windows::Win32::System::ProcessStatus::MODULEINFO;
+
+use super::{Hook, HookData, HookImpl, HookTarget, InjectKind};
+use crate::asm::{handle_x86_asm_build, newmem_inject};
+use crate::memory::timeout::TimeoutPolicy;
+use crate::memory::Byte;
+use crate::process::module::module_by_name;
+use crate::process::SafeHandle;
+use crate::{memop_err, MemOpResult};
+
+/// Builds a `Hook`, overriding parts of its `HookImpl` at runtime.
+///
+/// Every option left unset falls back to the `HookImpl`, so `Hook::builder(handle, MyHook).build()`
+/// is the same as `Hook::new(handle, MyHook)`.
+///
+/// # Example
+/// ```rust,norun
+/// let hook = Hook::builder(handle, MyHook)
+///     .module("game_x64.exe")
+///     .pattern(prepare_pattern(&config.signature))
+///     .inject_kind(InjectKind::Call)
+///     .build()?;
+/// hook.hook(Duration::from_secs(1))?;
+/// ```
+pub struct HookBuilder {
+    handle: SafeHandle,
+    hook_impl: Box<dyn HookImpl>,
+    module: Option<&'static str>,
+    pattern: Option<Vec<Byte>>,
+    alignment: Option<usize>,
+    inject_kind: Option<InjectKind>,
+    address: Option<usize>,
+    timeouts: Option<TimeoutPolicy>,
+}
+
+impl HookBuilder {
+    /// Same as `Hook::builder()`.
+    pub fn new(handle: SafeHandle, hook_impl: impl HookImpl + 'static) -> Self {
+        HookBuilder {
+            handle,
+            hook_impl: Box::new(hook_impl),
+            module: None,
+            pattern: None,
+            alignment: None,
+            inject_kind: None,
+            address: None,
+            timeouts: None,
+        }
+    }
+
+    /// Overrides `HookImpl::module_name()`, the module scanned and whose base the hook is relative to.
+    pub fn module(mut self, module: &'static str) -> Self {
+        self.module = Some(module);
+        self
+    }
+
+    /// Overrides `HookImpl::pattern()`, see `Hook::with_pattern()`.
+    pub fn pattern(mut self, pattern: Vec<Byte>) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    /// Overrides `HookImpl::hook_mem_alignment()`, a power of two.
+    pub fn alignment(mut self, alignment: usize) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Overrides `HookImpl::inject_kind()`.
+    pub fn inject_kind(mut self, inject_kind: InjectKind) -> Self {
+        self.inject_kind = Some(inject_kind);
+        self
+    }
+
+    /// Injects at a known address instead of scanning for the pattern, like `Hook::new_at_export()`.
+    pub fn address(mut self, address: usize) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    /// Sets the timeouts memory operations through the hook fall back to, see `HookOps::set_timeouts()`.
+    pub fn timeouts(mut self, timeouts: TimeoutPolicy) -> Self {
+        self.timeouts = Some(timeouts);
+        self
+    }
+
+    /// Resolves the module and allocates the hook's memory, without hooking yet.
+    pub fn build(self) -> MemOpResult<Arc<Hook>> {
+        let hook_impl = OverriddenImpl {
+            inner: self.hook_impl,
+            module: self.module,
+            alignment: self.alignment,
+            inject_kind: self.inject_kind,
+        };
+
+        let module_name = hook_impl.module_name();
+        let module = module_by_name(&self.handle, module_name, true, None)?
+            .ok_or(memop_err!("No module named \"{}\".", module_name))?;
+
+        let target = match self.address {
+            Some(addr) => HookTarget::Address(addr),
+            None => HookTarget::Pattern,
+        };
+
+        let hook = Hook::from_parts(self.handle, hook_impl, module.0 as usize, target)?;
+
+        let mut data = hook.data.write();
+        if let Some(pattern) = self.pattern {
+            data.pattern = pattern;
+        }
+        if let Some(timeouts) = self.timeouts {
+            data.timeouts = timeouts;
+        }
+        drop(data);
+
+        Ok(hook)
+    }
+}
+
+impl Hook {
+    /// Starts building a hook with runtime overrides, see `HookBuilder`.
+    pub fn builder(handle: SafeHandle, hook_impl: impl HookImpl + 'static) -> HookBuilder {
+        HookBuilder::new(handle, hook_impl)
+    }
+}
+
+/// A `HookImpl` with the options set on a `HookBuilder` taking precedence.
+#[derive(Clone)]
+struct OverriddenImpl {
+    inner: Box<dyn HookImpl>,
+    module: Option<&'static str>,
+    alignment: Option<usize>,
+    inject_kind: Option<InjectKind>,
+}
+
+impl HookImpl for OverriddenImpl {
+    fn pattern(&self) -> &'static [Byte] {
+        self.inner.pattern()
+    }
+
+    fn var_size(&self) -> usize {
+        self.inner.var_size()
+    }
+
+    fn hook_alloc_size(&self) -> usize {
+        self.inner.hook_alloc_size()
+    }
+
+    fn grow_hook_mem(&self) -> bool {
+        self.inner.grow_hook_mem()
+    }
+
+    fn hook_mem_alignment(&self) -> usize {
+        self.alignment.unwrap_or_else(|| self.inner.hook_mem_alignment())
+    }
+
+    fn module_name(&self) -> &'static str {
+        self.module.unwrap_or_else(|| self.inner.module_name())
+    }
+
+    fn scan_range(&self, module_info: &MODULEINFO) -> (usize, usize) {
+        self.inner.scan_range(module_info)
+    }
+
+    fn inject_kind(&self) -> InjectKind {
+        self.inject_kind.unwrap_or_else(|| self.inner.inject_kind())
+    }
+
+    fn build_jmp(&self, hook_data: &HookData) -> Result<Vec<u8>> {
+        // A custom `build_jmp` of the inner impl only applies while its inject kind isn't overridden.
+        match self.inject_kind {
+            Some(kind) => handle_x86_asm_build(newmem_inject(hook_data, kind)?),
+            None => self.inner.build_jmp(hook_data),
+        }
+    }
+
+    fn build_hook(&self, hook_data: &HookData) -> Result<Vec<u8>> {
+        self.inner.build_hook(hook_data)
+    }
+}
@@ -1,15 +1,26 @@
 #[cfg(feature = "async")]
 pub mod async_ext;
+pub mod builder;
 
-use crate::asm::{handle_x86_asm_build, newmem_jmp};
-use crate::memory::utils::allocate_memory;
+pub use builder::HookBuilder;
+
+use crate::asm::{handle_x86_asm_build, newmem_inject};
+use crate::memory::utils::{
+    allocate_memory, allocate_memory_aligned, check_cancelled, flush_instruction_cache,
+    CancellationToken,
+};
 
 use crate::memory::{
-    read::read_bytes, utils::change_memory_protection, write::write_bytes, Byte, MemOpContext,
-    MemoryRegion,
+    read::{read_bytes, read_bytes_cancellable, read_committed_cancellable},
+    timeout::TimeoutPolicy,
+    utils::change_memory_protection,
+    write::{changed_spans, write_bytes, write_bytes_cancellable},
+    Byte, MemOpContext, MemoryRegion,
+};
+use crate::process::module::{
+    get_export_address, get_export_by_ordinal, get_module_info, module_by_name,
 };
-use crate::process::module::{get_module_info, module_by_name};
-use crate::process::pattern::{create_unhook_bytes, find_pattern_in_bytes};
+use crate::process::pattern::{create_unhook_bytes, find_pattern_in_bytes, resolve_signature};
 use crate::process::SafeHandle;
 
 use anyhow::{anyhow, Result};
@@ -41,11 +52,13 @@ macro_rules! impl_hook_clone {
 /// Top-level structure for a process memory hook.
 /// 
 /// Runtime data is separated from compile-time, which is separated from implementation.
+///
+/// Dropping the last clone unhooks before hook_mem and var_mem are freed. If unhooking fails, they are leaked.
 #[derive(Clone)]
 pub struct Hook {
     pub handle: SafeHandle,
     pub data: std::sync::Arc<parking_lot::RwLock<HookData>>,
-    pub hook_impl: Box<dyn HookImpl>,
+    pub hook_impl: std::sync::Arc<parking_lot::RwLock<Box<dyn HookImpl>>>,
 }
 
 pub trait CloneHookImpl {
@@ -72,48 +85,454 @@ impl Hook {
         handle: SafeHandle,
         hook_impl: impl HookImpl + 'static,
     ) -> MemOpResult<std::sync::Arc<Self>> {
-        // Initialize with default HookData
         let maybe_module = module_by_name(&handle, hook_impl.module_name(), true, None)?;
         let module = maybe_module.ok_or(anyhow!("Could not get module Zhol.exe."))?;
+
+        Self::from_parts(handle, hook_impl, module.0 as usize, HookTarget::Pattern)
+    }
+
+    /// Creates a hook injecting at the address of a function exported by a module, skipping pattern scanning.
+    ///
+    /// The export is resolved through the module's PE export table when the hook is created.
+    /// `HookImpl::pattern()` is only used to determine how many bytes are saved for unhooking, and may be empty.
+    pub fn new_at_export(
+        handle: SafeHandle,
+        hook_impl: impl HookImpl + 'static,
+        module: &str,
+        export_name: &str,
+    ) -> MemOpResult<std::sync::Arc<Self>> {
+        let module = module_by_name(&handle, module, true, None)?
+            .ok_or(memop_err!("No module named \"{}\".", module))?;
+        let addr = get_export_address(&handle, module, export_name, None)?;
+
+        Self::from_parts(handle, hook_impl, module.0 as usize, HookTarget::Address(addr))
+    }
+
+    /// Same as `new_at_export()`, but resolves a function exported by ordinal, for exports without a name.
+    pub fn new_at_ordinal(
+        handle: SafeHandle,
+        hook_impl: impl HookImpl + 'static,
+        module: &str,
+        ordinal: u32,
+    ) -> MemOpResult<std::sync::Arc<Self>> {
+        let module = module_by_name(&handle, module, true, None)?
+            .ok_or(memop_err!("No module named \"{}\".", module))?;
+        let addr = get_export_by_ordinal(&handle, module, ordinal, None)?;
+
+        Self::from_parts(handle, hook_impl, module.0 as usize, HookTarget::Address(addr))
+    }
+
+    /// Creates a hook injecting at the address a signature from a signature database resolves to.
+    ///
+    /// The signature is resolved when the hook is created, like `new_at_export()`.
+    /// `HookImpl::pattern()` is only used to determine how many bytes are saved for unhooking, and may be empty.
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// let signatures = load_signatures("signatures.toml")?;
+    /// let hook = Hook::new_at_signature(handle, MyHook, &signatures["update_player"])?;
+    /// ```
+    pub fn new_at_signature(
+        handle: SafeHandle,
+        hook_impl: impl HookImpl + 'static,
+        signature: &crate::tools::SignatureDef,
+    ) -> MemOpResult<std::sync::Arc<Self>> {
+        let module = module_by_name(&handle, &signature.module, true, None)?
+            .ok_or(memop_err!("No module named \"{}\".", signature.module))?;
+        let addr = resolve_signature(&handle, module, &signature.pattern, signature.anchor)?;
+
+        Self::from_parts(handle, hook_impl, module.0 as usize, HookTarget::Address(addr))
+    }
+
+    /// Creates a hook from a match found by a manual scan, e.g. `pattern_scan()`, skipping the scan on install.
+    ///
+    /// `found_bytes` are the original bytes at `match_addr`, restored on unhook, so they must cover the jump.
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// let (offset, found_bytes) = pattern_scan(&handle, PATTERN, origin, size, Some(1))?.remove(0);
+    /// let hook = Hook::from_match(handle, MyHook, origin + offset, found_bytes)?;
+    /// hook.hook(Duration::from_secs(1))?;
+    /// ```
+    pub fn from_match(
+        handle: SafeHandle,
+        hook_impl: impl HookImpl + 'static,
+        match_addr: usize,
+        found_bytes: Vec<u8>,
+    ) -> MemOpResult<std::sync::Arc<Self>> {
+        let module = module_by_name(&handle, hook_impl.module_name(), true, None)?
+            .ok_or(memop_err!("No module named \"{}\".", hook_impl.module_name()))?;
+
+        let hook = Self::from_parts(handle, hook_impl, module.0 as usize, HookTarget::Address(match_addr))?;
+
+        let mut data = hook.data.write();
+        data.addr = Some(match_addr);
+
+        let jmp_size = data.get_jmp_size(hook.hook_impl.read().as_ref())?;
+        if found_bytes.len() < jmp_size {
+            return Err(memop_err!(
+                "Match has \"{:#X}\" found bytes, but the jump overwrites \"{jmp_size:#X}\".",
+                found_bytes.len()
+            ));
+        }
+        data.found_bytes = Some(found_bytes);
+        drop(data);
+
+        Ok(hook)
+    }
+
+    /// Overrides `HookImpl::pattern()` with a pattern parsed at runtime, e.g. loaded from a config file.
+    ///
+    /// Call this before hooking, as the inject point and unhook bytes are resolved against the pattern.
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// let hook = Hook::new(handle, MyHook)?.with_pattern(prepare_pattern(&config.signature));
+    /// ```
+    pub fn with_pattern(self: std::sync::Arc<Self>, pattern: Vec<Byte>) -> std::sync::Arc<Self> {
+        self.data.write().pattern = pattern;
+        self
+    }
+
+    fn from_parts(
+        handle: SafeHandle,
+        hook_impl: impl HookImpl + 'static,
+        module_addr: usize,
+        target: HookTarget,
+    ) -> MemOpResult<std::sync::Arc<Self>> {
         let data = HookData {
-            module_addr: module.0 as usize,
-            hook_mem: allocate_memory(&handle, hook_impl.hook_alloc_size())?,
+            module_addr,
+            hook_mem: allocate_memory_aligned(
+                &handle,
+                hook_impl.hook_alloc_size(),
+                hook_impl.hook_mem_alignment(),
+            )?,
             var_mem: allocate_memory(&handle, hook_impl.var_size())?,
             pattern: hook_impl.pattern().to_vec(),
             var_size: hook_impl.var_size(),
             hook_alloc_size: hook_impl.hook_alloc_size(),
+            target,
             addr: None,
             found_bytes: None,
+            timeouts: TimeoutPolicy::default(),
+            hook_mem_checksum: None,
         };
 
         let hook_self = Self {
             handle,
             data: std::sync::Arc::new(parking_lot::RwLock::new(data)),
-            hook_impl: Box::new(hook_impl),
+            hook_impl: std::sync::Arc::new(parking_lot::RwLock::new(Box::new(hook_impl))),
         };
 
         Ok(std::sync::Arc::new(hook_self))
     }
+
+    /// Finds the inject point by scanning the hook's module for its pattern.
+    fn scan_inject_point(
+        &self,
+        timeout: Duration,
+        cancel: Option<&CancellationToken>,
+    ) -> MemOpResult<()> {
+        let module = match module_by_name(
+            &self.handle,
+            self.hook_impl.read().module_name(),
+            true,
+            Some(timeout),
+        )? {
+            Some(m) => m,
+            None => {
+                return Err(crate::memop_err!(
+                    "No module named \"{}\".",
+                    self.hook_impl.read().module_name()
+                ))
+            }
+        };
+
+        let timeouts = self.data.read().timeouts;
+        let module_info: MODULEINFO = get_module_info(&self.handle, module, timeouts.acquire(None))?;
+        let (start, size) = self.hook_impl.read().scan_range(&module_info);
+        let scan_base = module.0 as usize + start;
+
+        // Best effort, as ranges with uncommitted gaps can't be reprotected as a whole.
+        _ = change_memory_protection(
+            &self.handle,
+            scan_base,
+            size,
+            timeouts.acquire(None),
+            PAGE_READWRITE,
+        );
+
+        let bytes =
+            read_committed_cancellable(&self.handle, scan_base, size, timeouts.scan(None), cancel)?;
+
+        let matches = find_pattern_in_bytes(bytes, self.data.read().pattern.clone(), Some(1))?;
+
+        // Use write lock to modify data
+        let mut data = self.data.write();
+        (data.addr, data.found_bytes) = match matches.first() {
+            Some((a, b)) => (Some(scan_base + a.to_owned()), Some(b.to_owned())),
+            None => return MemOpResult::Err(MemOpError::PatternNotFound),
+        };
+
+        Ok(())
+    }
+
+    /// Uses a known inject point, saving the bytes the jump will overwrite.
+    fn read_inject_point(
+        &self,
+        addr: usize,
+        timeout: Duration,
+        cancel: Option<&CancellationToken>,
+    ) -> MemOpResult<()> {
+        let mut data = self.data.write();
+
+        // Bytes saved by a previous install are the originals; re-reading would save our own jump.
+        if data.addr == Some(addr) && data.found_bytes.is_some() {
+            return Ok(());
+        }
+
+        data.addr = Some(addr);
+        let patch_size = data
+            .get_jmp_size(self.hook_impl.read().as_ref())?
+            .max(data.pattern.len());
+
+        data.found_bytes = Some(read_bytes_cancellable(
+            &self.handle,
+            addr,
+            patch_size,
+            Some(timeout),
+            cancel,
+        )?);
+
+        Ok(())
+    }
+
+    /// Builds the hook bytes, growing `hook_mem` if they don't fit and the impl allows it.
+    ///
+    /// Writing a hook larger than `hook_mem` would corrupt whatever follows the allocation, so this errors otherwise.
+    fn build_fitting_hook(&self) -> MemOpResult<Vec<u8>> {
+        loop {
+            // data is locked before hook_impl everywhere, so replace_impl() can't deadlock against this.
+            let data = self.data.read();
+            let hook_bytes = self.hook_impl.read().build_hook(&data)?;
+            let mem_size = data.hook_mem.size;
+            drop(data);
+
+            if hook_bytes.len() <= mem_size {
+                return Ok(hook_bytes);
+            }
+
+            if !self.hook_impl.read().grow_hook_mem() {
+                return Err(memop_err!(
+                    "Built hook is \"{:#X}\" bytes, which does not fit in hook_mem of \"{:#X}\" bytes.",
+                    hook_bytes.len(),
+                    mem_size
+                ));
+            }
+
+            // The hook bytes may reference hook_mem, so they are rebuilt against the new region on the next pass.
+            let new_size = hook_bytes.len().next_multiple_of(0x1000);
+            let region =
+                allocate_memory_aligned(&self.handle, new_size, self.hook_impl.read().hook_mem_alignment())?;
+
+            let mut data = self.data.write();
+            data.hook_mem = region;
+            data.hook_alloc_size = new_size;
+        }
+    }
+}
+
+impl Drop for Hook {
+    fn drop(&mut self) {
+        // Clones share `data`, and whoever else holds it keeps the regions alive, so only the last one unhooks.
+        if std::sync::Arc::strong_count(&self.data) != 1 {
+            return;
+        }
+
+        let installed = {
+            let data = self.data.read();
+            jump_installed(&self.handle, self.hook_impl.read().as_ref(), &data, Some(DROP_UNHOOK_TIMEOUT))
+        };
+
+        let unhooked = match installed {
+            Ok(true) => self.unhook(DROP_UNHOOK_TIMEOUT),
+            Ok(false) => Ok(()),
+            Err(e) => Err(e),
+        };
+
+        // Freeing hook_mem and var_mem under a live jump would crash the target on its next pass, so leak them instead.
+        if unhooked.is_err() {
+            let data = self.data.read();
+            std::mem::forget(data.hook_mem.clone());
+            std::mem::forget(data.var_mem.clone());
+        }
+    }
 }
 
 unsafe impl Send for Hook {}
 unsafe impl Sync for Hook {}
 
+/// Timeout for each step of unhooking when the last clone of a `Hook` is dropped.
+const DROP_UNHOOK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Returns whether the inject point currently holds the hook's jump, i.e. whether the hook is installed.
+fn jump_installed(
+    handle: &SafeHandle,
+    hook_impl: &dyn HookImpl,
+    data: &HookData,
+    timeout: Option<Duration>,
+) -> MemOpResult<bool> {
+    match data.addr {
+        Some(addr) => {
+            let jump_bytes = hook_impl.build_jmp(data)?;
+            Ok(read_bytes(handle, addr, jump_bytes.len(), timeout)? == jump_bytes)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Writes a hook's code and then the jump redirecting the inject point to it.
+///
+/// Invariant: the inject jump is only written once `hook_bytes` were fully written to `hook_mem_addr`
+/// and the instruction cache was flushed for them. Any failure (or cancellation) before that returns
+/// without touching the inject point, so the target never jumps into partially written code.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `hook_mem_addr` - Address the hook's code is written to
+/// * `hook_bytes` - The hook's assembled code
+/// * `inject_addr` - Address of the inject point
+/// * `jump_bytes` - The jump written to the inject point
+/// * `timeout` - Optional timeout for each step
+/// * `cancel` - Optional token checked between steps
+pub fn install_hook_bytes(
+    handle: &SafeHandle,
+    hook_mem_addr: usize,
+    hook_bytes: &[u8],
+    inject_addr: usize,
+    jump_bytes: &[u8],
+    timeout: Option<Duration>,
+    cancel: Option<&CancellationToken>,
+) -> MemOpResult<()> {
+    write_bytes_cancellable(handle, hook_mem_addr, hook_bytes, timeout, cancel)?;
+    flush_instruction_cache(handle, hook_mem_addr, hook_bytes.len(), timeout)?;
+
+    write_bytes_cancellable(handle, inject_addr, jump_bytes, timeout, cancel)?;
+    flush_instruction_cache(handle, inject_addr, jump_bytes.len(), timeout)
+}
+
 /// Hook-agnostic operations so the hook can be meaningfully interacted with in top-level logic.
 /// 
 /// Provides common functionality like hooking, unhooking, and inner specification retreival.
 pub trait HookOps: Send + Sync {
     fn handle(&self) -> SafeHandle;
     fn data(&self) -> &std::sync::Arc<parking_lot::RwLock<HookData>>;
-    fn hook_impl(&self) -> &Box<dyn HookImpl>;
+    fn hook_impl(&self) -> parking_lot::RwLockReadGuard<'_, Box<dyn HookImpl>>;
 
     // #[cfg(feature = "async")]
     // async fn async_hook(&self, timeout: Duration) -> crate::MemOpResult<()>;
     fn hook(&self, timeout: Duration) -> MemOpResult<()>;
 
+    /// Same as `hook()`, but aborts with `MemOpError::Cancelled` if `cancel` is set before any of its steps.
+    ///
+    /// The default implementation only checks the token once before hooking.
+    fn hook_cancellable(
+        &self,
+        timeout: Duration,
+        cancel: Option<&CancellationToken>,
+    ) -> MemOpResult<()> {
+        check_cancelled(cancel)?;
+        self.hook(timeout)
+    }
+
     // #[cfg(feature = "async")]
     // async fn async_unhook(&self, timeout: Duration) -> crate::MemOpResult<()>;
     fn unhook(&self, timeout: Duration) -> MemOpResult<()>;
+
+    /// Swaps in a new hook implementation, reinstalling the hook with it if it is currently installed.
+    ///
+    /// The new hook is built against the existing hook_mem and var_mem before anything is written,
+    /// so a failing build leaves the old hook installed. Unhooking, swapping and re-hooking all happen
+    /// under the data write lock, so no other operation observes the hook half-replaced.
+    ///
+    /// # Arguments
+    /// * `new_impl` - The implementation replacing the current one
+    /// * `timeout` - Timeout for each memory operation
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// hook.replace_impl(Box::new(GodModeV2), Duration::from_secs(1))?;
+    /// ```
+    fn replace_impl(&self, new_impl: Box<dyn HookImpl>, timeout: Duration) -> MemOpResult<()>;
+
+    /// Reads the bytes currently at the inject point, as many as `unhook()` restores.
+    ///
+    /// Compare against `HookData.found_bytes` or `HookImpl::build_jmp()` to see whether the hook is in place.
+    fn read_current_inject_bytes(&self, timeout: Duration) -> MemOpResult<Vec<u8>> {
+        let data = self.data().read();
+
+        let inject_addr = data
+            .addr
+            .ok_or(memop_err!("No inject point resolved, hook first."))?;
+        let found_bytes = data.found_bytes.as_ref().ok_or(memop_err!(
+            "Inject point read without pattern scanned and match found."
+        ))?;
+
+        read_bytes(&self.handle(), inject_addr, found_bytes.len(), Some(timeout))
+    }
+
+    /// Unhooks by restoring only the bytes the hook's jump overwrote, leaving the rest of the span alone.
+    ///
+    /// Unlike `unhook()`, bytes the target changed after the jump aren't overwritten. The jump is only
+    /// restored if it is still fully in place; if the target changed part of it, nothing is written and
+    /// an error is returned, since neither the jump nor the original bytes can be trusted there.
+    /// The data lock is held throughout, so no other hook operation runs in between.
+    ///
+    /// # Returns
+    /// Returns the byte ranges (relative to the inject point) that were restored, empty if already unhooked
+    fn unhook_minimal(&self, timeout: Duration) -> MemOpResult<Vec<std::ops::Range<usize>>> {
+        let handle = self.handle();
+        let data = self.data().write();
+
+        let inject_addr = match data.addr {
+            None => return Ok(Vec::new()),
+            Some(a) => a,
+        };
+
+        let found_bytes = data.found_bytes.as_ref().ok_or(memop_err!(
+            "Unhook called without pattern scanned and match found."
+        ))?;
+        let original = match data.target {
+            HookTarget::Pattern => create_unhook_bytes(&data.pattern, found_bytes),
+            HookTarget::Address(_) => found_bytes.clone(),
+        };
+        let jump_bytes = self.hook_impl().build_jmp(&data)?;
+
+        let original = original.get(..jump_bytes.len()).ok_or(memop_err!(
+            "Jump of \"{:#X}\" bytes is longer than the \"{:#X}\" bytes found at the inject point.",
+            jump_bytes.len(),
+            original.len()
+        ))?;
+        let current = read_bytes(&handle, inject_addr, jump_bytes.len(), Some(timeout))?;
+
+        if current == original {
+            return Ok(Vec::new());
+        }
+        if current != jump_bytes {
+            return Err(memop_err!(
+                "Inject point at \"{inject_addr:#X}\" holds neither the hook's jump nor the original bytes."
+            ));
+        }
+
+        let spans = changed_spans(&current, original);
+        for span in &spans {
+            write_bytes(&handle, inject_addr + span.start, &original[span.clone()], Some(timeout))?;
+        }
+        flush_instruction_cache(&handle, inject_addr, jump_bytes.len(), Some(timeout))?;
+
+        Ok(spans)
+    }
+
     // pub struct MemOpContext {
     //     pub addr: usize,
     //     pub offset: usize,
@@ -121,11 +540,108 @@ pub trait HookOps: Send + Sync {
     //     pub timeout: Option<Duration>,
     // }
     //
+    /// Returns the timeout policy memory operations through this hook fall back to.
+    fn timeouts(&self) -> TimeoutPolicy {
+        self.data().read().timeouts
+    }
+
+    /// Replaces the timeout policy memory operations through this hook fall back to.
+    fn set_timeouts(&self, timeouts: TimeoutPolicy) {
+        self.data().write().timeouts = timeouts;
+    }
+
+    /// Moves var_mem to a new, larger region, keeping its contents.
+    ///
+    /// If the hook is currently installed, its code is rebuilt against the new address and rewritten in place.
+    /// Values written to the old var_mem while it is being copied are lost, so pause writers first.
+    /// The old region is freed once nothing references it anymore.
+    fn grow_var_mem(&self, new_size: usize) -> MemOpResult<()> {
+        let handle = self.handle();
+        let timeouts = self.timeouts();
+        let old_size = self.data().read().var_mem.size;
+
+        if new_size <= old_size {
+            return Err(memop_err!(
+                "New var_mem size \"{new_size:#X}\" is not larger than the current \"{old_size:#X}\"."
+            ));
+        }
+
+        let region = allocate_memory(&handle, new_size)?;
+
+        let mut data = self.data().write();
+        let contents = read_bytes(&handle, data.var_mem.addr, data.var_mem.size, timeouts.read)?;
+        write_bytes(&handle, region.addr, &contents, timeouts.write)?;
+
+        let hooked = jump_installed(&handle, self.hook_impl().as_ref(), &data, timeouts.read)?;
+
+        if hooked {
+            // The rebuilt code is written before the new region is committed to `data`,
+            // so any failure leaves the old region in use rather than freeing memory the hook addresses.
+            let mut staged = data.clone();
+            staged.var_mem = region.clone();
+            staged.var_size = new_size;
+
+            let hook_bytes = self.hook_impl().build_hook(&staged)?;
+            if hook_bytes.len() > data.hook_mem.size {
+                return Err(memop_err!(
+                    "Rebuilt hook is \"{:#X}\" bytes, which does not fit in hook_mem of \"{:#X}\" bytes.",
+                    hook_bytes.len(),
+                    data.hook_mem.size
+                ));
+            }
+
+            let written = write_bytes(&handle, data.hook_mem.addr, &hook_bytes, timeouts.write)
+                .and_then(|_| {
+                    flush_instruction_cache(&handle, data.hook_mem.addr, hook_bytes.len(), timeouts.write)
+                });
+            if let Err(e) = written {
+                // The code may be partly rewritten to address the new region, so neither may be freed.
+                std::mem::forget(region);
+                return Err(e);
+            }
+        }
+
+        data.var_mem = region;
+        data.var_size = new_size;
+
+        if hooked {
+            let hook_mem = read_bytes(&handle, data.hook_mem.addr, data.hook_mem.size, timeouts.read)?;
+            data.hook_mem_checksum = Some(fnv1a_64(&hook_mem));
+        }
+
+        Ok(())
+    }
+
+    /// Hashes the current contents of hook_mem.
+    ///
+    /// Compare against `HookData.hook_mem_checksum`, taken right after the hook was installed;
+    /// a mismatch means the hook code was modified by someone else.
+    fn hook_mem_checksum(&self, timeout: Option<Duration>) -> MemOpResult<u64> {
+        let region = self.data().read().hook_mem.clone();
+        let bytes = read_bytes(&self.handle(), region.addr, region.size, self.timeouts().read(timeout))?;
+
+        Ok(fnv1a_64(&bytes))
+    }
+
     /// Creates MemOpContext for a default memory operation originating from the base of the hook
     fn ctx(&self, offset: usize, at_pointer: bool, timeout: Option<Duration>) -> MemOpContext {
         let data = self.data().read();
         MemOpContext::new(data.var_mem.addr, offset, at_pointer, timeout)
     }
+
+    /// Disassembles the original bytes at the inject point, i.e. the instructions overwritten by the hook.
+    ///
+    /// Requires the hook to have been installed (or its inject point otherwise found).
+    #[cfg(feature = "disassembly")]
+    fn disassemble_original(&self) -> MemOpResult<Vec<String>> {
+        let data = self.data().read();
+        let addr = data.get_addr()?;
+        let found_bytes = data.found_bytes.as_ref().ok_or(memop_err!(
+            "disassemble_original() called without pattern scanned and match found."
+        ))?;
+
+        crate::asm::disasm::disassemble(found_bytes, addr, crate::asm::disasm::HOOK_BITNESS)
+    }
 }
 use crate::{memop_err, MemOpError, MemOpResult};
 impl HookOps for Hook {
@@ -137,71 +653,51 @@ impl HookOps for Hook {
         self.handle.clone()
     }
 
-    fn hook_impl(&self) -> &Box<dyn HookImpl> {
-        &self.hook_impl
+    fn hook_impl(&self) -> parking_lot::RwLockReadGuard<'_, Box<dyn HookImpl>> {
+        self.hook_impl.read()
     }
 
     // Modified to take &self instead of &mut self
     fn hook(&self, timeout: Duration) -> MemOpResult<()> {
-        let module = match module_by_name(
-            &self.handle,
-            &self.hook_impl.module_name(),
-            true,
-            Some(timeout),
-        )? {
-            Some(m) => m,
-            None => {
-                return Err(crate::memop_err!(
-                    "No module named \"{}\".",
-                    &self.hook_impl.module_name()
-                ))
-            }
-        };
-
-        let module_info: MODULEINFO = get_module_info(&self.handle, module, None)?;
-        change_memory_protection(
-            &self.handle,
-            module.0 as usize,
-            module_info.SizeOfImage as usize,
-            None,
-            PAGE_READWRITE,
-        )?;
-
-        let bytes = read_bytes(
-            &self.handle,
-            module.0 as usize,
-            module_info.SizeOfImage as usize,
-            None,
-        )?;
-
-        let matches = find_pattern_in_bytes(bytes, self.data.read().pattern.clone())?;
+        self.hook_cancellable(timeout, None)
+    }
 
-        // Use write lock to modify data
-        {
-            let mut data = self.data.write();
-            (data.addr, data.found_bytes) = match matches.first() {
-                Some((a, b)) => (Some(module.0 as usize + a.to_owned()), Some(b.to_owned())),
-                None => return MemOpResult::Err(MemOpError::PatternNotFound),
-            };
+    fn hook_cancellable(
+        &self,
+        timeout: Duration,
+        cancel: Option<&CancellationToken>,
+    ) -> MemOpResult<()> {
+        check_cancelled(cancel)?;
+        let target = self.data.read().target.clone();
+        match target {
+            HookTarget::Pattern => self.scan_inject_point(timeout, cancel)?,
+            HookTarget::Address(addr) => self.read_inject_point(addr, timeout, cancel)?,
         }
 
+        check_cancelled(cancel)?;
+        let hook_bytes = self.build_fitting_hook()?;
+
         // Now read the data
         let data_read = self.data.read();
-        let hook_bytes = self.hook_impl.build_hook(&data_read)?;
-        let jump_bytes = self.hook_impl.build_jmp(&data_read)?;
+        let jump_bytes = self.hook_impl.read().build_jmp(&data_read)?;
 
         let addr = data_read.addr.ok_or(anyhow!(
             "Inject point address was not found. This should not be possible."
         ))?;
 
-        write_bytes(
+        install_hook_bytes(
             &self.handle,
-            data_read.hook_mem.addr as usize,
+            data_read.hook_mem.addr,
             &hook_bytes,
+            addr,
+            &jump_bytes,
             Some(timeout),
+            cancel,
         )?;
+        drop(data_read);
 
-        write_bytes(&self.handle, addr, &jump_bytes, Some(timeout))?;
+        let checksum = self.hook_mem_checksum(Some(timeout))?;
+        self.data.write().hook_mem_checksum = Some(checksum);
 
         Ok(())
     }
@@ -216,12 +712,12 @@ impl HookOps for Hook {
 
         match &data_read.found_bytes {
             Some(found_bytes) => {
-                write_bytes(
-                    &self.handle,
-                    inject_addr,
-                    &create_unhook_bytes(self.hook_impl.pattern(), found_bytes),
-                    Some(timeout),
-                )?;
+                let unhook_bytes = match data_read.target {
+                    HookTarget::Pattern => create_unhook_bytes(&data_read.pattern, found_bytes),
+                    HookTarget::Address(_) => found_bytes.clone(),
+                };
+
+                write_bytes(&self.handle, inject_addr, &unhook_bytes, Some(timeout))?;
             }
             None => {
                 return Err(memop_err!(
@@ -232,6 +728,95 @@ impl HookOps for Hook {
 
         Ok(())
     }
+
+    fn replace_impl(&self, new_impl: Box<dyn HookImpl>, timeout: Duration) -> MemOpResult<()> {
+        let mut data = self.data.write();
+        let mut hook_impl = self.hook_impl.write();
+
+        let installed = jump_installed(&self.handle, hook_impl.as_ref(), &data, Some(timeout))?;
+
+        if !installed {
+            *hook_impl = new_impl;
+            return Ok(());
+        }
+
+        let (addr, found_bytes) = match (data.addr, &data.found_bytes) {
+            (Some(addr), Some(found_bytes)) => (addr, found_bytes),
+            _ => {
+                return Err(memop_err!(
+                    "Replace called without pattern scanned and match found."
+                ))
+            }
+        };
+
+        let hook_bytes = new_impl.build_hook(&data)?;
+        if hook_bytes.len() > data.hook_mem.size {
+            return Err(memop_err!(
+                "Built hook is \"{:#X}\" bytes, which does not fit in hook_mem of \"{:#X}\" bytes.",
+                hook_bytes.len(),
+                data.hook_mem.size
+            ));
+        }
+
+        let jump_bytes = new_impl.build_jmp(&data)?;
+        if jump_bytes.len() > found_bytes.len() {
+            return Err(memop_err!(
+                "New jump is \"{:#X}\" bytes, but only \"{:#X}\" bytes were saved at the inject point.",
+                jump_bytes.len(),
+                found_bytes.len()
+            ));
+        }
+
+        let unhook_bytes = match data.target {
+            HookTarget::Pattern => create_unhook_bytes(&data.pattern, found_bytes),
+            HookTarget::Address(_) => found_bytes.clone(),
+        };
+
+        write_bytes(&self.handle, addr, &unhook_bytes, Some(timeout))?;
+        flush_instruction_cache(&self.handle, addr, unhook_bytes.len(), Some(timeout))?;
+        *hook_impl = new_impl;
+
+        install_hook_bytes(
+            &self.handle,
+            data.hook_mem.addr,
+            &hook_bytes,
+            addr,
+            &jump_bytes,
+            Some(timeout),
+            None,
+        )?;
+
+        let hook_mem = read_bytes(&self.handle, data.hook_mem.addr, data.hook_mem.size, Some(timeout))?;
+        data.hook_mem_checksum = Some(fnv1a_64(&hook_mem));
+
+        Ok(())
+    }
+}
+
+/// 64-bit FNV-1a hash, stable across builds unlike `std`'s `DefaultHasher`.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Instruction a hook writes at its inject point to enter hook_mem.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InjectKind {
+    /// `jmp rel32` (`E9`), the hook jumps back itself
+    #[default]
+    Jmp,
+    /// `call rel32` (`E8`), the hook can `ret` to the instruction after the inject point
+    Call,
+}
+
+/// Determines how the inject point of a hook is found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HookTarget {
+    /// Scan the hook's module for `HookData.pattern`.
+    Pattern,
+    /// Inject at an already resolved address, e.g. an export.
+    Address(usize),
 }
 
 /// The runtime data for a process memory hook.
@@ -246,8 +831,13 @@ pub struct HookData {
     pub pattern: Vec<Byte>,
     pub var_size: usize,
     pub hook_alloc_size: usize,
+    pub target: HookTarget,
     pub addr: Option<usize>,
     pub found_bytes: Option<Vec<u8>>,
+    /// Timeouts used by memory operations through this hook that don't specify their own
+    pub timeouts: TimeoutPolicy,
+    /// Checksum of hook_mem taken right after the hook was installed, see `HookOps::hook_mem_checksum()`
+    pub hook_mem_checksum: Option<u64>,
 }
 
 impl HookData {
@@ -271,6 +861,42 @@ impl HookData {
             .ok_or(anyhow!("Index \"{}\" not in range unhookbytes.", index))
             .copied()
     }
+
+    /// Resolves the target of the first instruction at the inject point if it is a relative `call`/`jmp`/`jcc`.
+    ///
+    /// Hooks placed on a function starting with a branch can use this to chain to where it originally went,
+    /// as the relative displacement in the stolen bytes is meaningless once they are moved to hook_mem.
+    ///
+    /// # Returns
+    /// Returns the absolute branch target, or `None` if the first instruction isn't a relative branch
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// // Inject point: E8 ?? ?? ?? ?? (call rel32)
+    /// let original = hook.data().read().first_instruction_target()?;
+    /// ```
+    #[cfg(feature = "disassembly")]
+    pub fn first_instruction_target(&self) -> MemOpResult<Option<usize>> {
+        use iced_x86::OpKind;
+
+        let addr = self.get_addr()?;
+        let found_bytes = self.found_bytes.as_ref().ok_or(memop_err!(
+            "first_instruction_target() called without pattern scanned and match found."
+        ))?;
+
+        let instructions =
+            crate::asm::disasm::decode(found_bytes, addr, crate::asm::disasm::HOOK_BITNESS)?;
+        let first = instructions
+            .first()
+            .ok_or(memop_err!("Inject point at \"{addr:#X}\" holds no whole instruction."))?;
+
+        Ok(match first.op0_kind() {
+            OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64 => {
+                Some(first.near_branch_target() as usize)
+            }
+            _ => None,
+        })
+    }
 }
 
 /// Defines the complile-time behavior of a process memory hook.
@@ -284,16 +910,201 @@ pub trait HookImpl: Send + Sync + CloneHookImpl {
     fn hook_alloc_size(&self) -> usize {
         0x1000
     }
+    /// Whether hook_mem may be reallocated larger when `build_hook` emits more than `hook_alloc_size` bytes.
+    fn grow_hook_mem(&self) -> bool {
+        false
+    }
+    /// Alignment hook_mem has to start at, a power of two. Defaults to no constraint.
+    fn hook_mem_alignment(&self) -> usize {
+        1
+    }
     fn module_name(&self) -> &'static str {
         "Zhol.exe"
     }
+    /// Range of the module scanned for the pattern, as a (module-relative start, length) pair.
+    ///
+    /// Defaults to the whole image. Restricting this (e.g. to `.text`) avoids false matches in data.
+    fn scan_range(&self, module_info: &MODULEINFO) -> (usize, usize) {
+        (0, module_info.SizeOfImage as usize)
+    }
+
+    /// Instruction written at the inject point to enter hook_mem. Defaults to `InjectKind::Jmp`.
+    ///
+    /// With `InjectKind::Call` the return address is pushed, so the hook can `ret` back behind the inject point.
+    fn inject_kind(&self) -> InjectKind {
+        InjectKind::Jmp
+    }
 
     // Hook building functionality
     fn build_jmp(&self, hook_data: &HookData) -> Result<Vec<u8>> {
-        let ops = newmem_jmp(hook_data)?;
+        let ops = newmem_inject(hook_data, self.inject_kind())?;
         handle_x86_asm_build(ops)
     }
 
     // Must be implemented by concrete hooks
     fn build_hook(&self, hook_data: &HookData) -> Result<Vec<u8>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_64_matches_reference_values() {
+        assert_eq!(fnv1a_64(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a_64(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[cfg(feature = "disassembly")]
+    fn current_process_hook_data(addr: usize, found_bytes: Vec<u8>) -> HookData {
+        let handle = crate::process::open_process(std::process::id()).unwrap();
+
+        HookData {
+            module_addr: 0x400000,
+            hook_mem: MemoryRegion::new(handle.clone(), 0x10).unwrap(),
+            var_mem: MemoryRegion::new(handle, 0x10).unwrap(),
+            pattern: Vec::new(),
+            var_size: 0x4,
+            hook_alloc_size: 0x10,
+            target: HookTarget::Address(addr),
+            addr: Some(addr),
+            found_bytes: Some(found_bytes),
+            timeouts: TimeoutPolicy::default(),
+            hook_mem_checksum: None,
+        }
+    }
+
+    #[cfg(feature = "disassembly")]
+    #[test]
+    fn first_instruction_target_current_process() {
+        // call rel32 with a displacement of 0x100, relative to the end of the call
+        let data = current_process_hook_data(0x401000, vec![0xE8, 0x00, 0x01, 0x00, 0x00, 0x90]);
+        assert_eq!(data.first_instruction_target().unwrap(), Some(0x401105));
+
+        // call rel32 with a negative displacement, back to the call itself
+        let data = current_process_hook_data(0x401000, vec![0xE8, 0xFB, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(data.first_instruction_target().unwrap(), Some(0x401000));
+
+        // mov eax, [ecx + 8]
+        let data = current_process_hook_data(0x401000, vec![0x8B, 0x41, 0x08]);
+        assert_eq!(data.first_instruction_target().unwrap(), None);
+    }
+
+    /// Hook whose code and jump only record the addresses they were built against.
+    #[derive(Clone)]
+    struct AddressHook {
+        marker: u8,
+    }
+
+    impl HookImpl for AddressHook {
+        fn pattern(&self) -> &'static [Byte] {
+            &[]
+        }
+
+        fn var_size(&self) -> usize {
+            0x10
+        }
+
+        fn hook_alloc_size(&self) -> usize {
+            0x40
+        }
+
+        fn build_jmp(&self, hook_data: &HookData) -> Result<Vec<u8>> {
+            Ok(hook_data.hook_mem.addr.to_le_bytes().to_vec())
+        }
+
+        fn build_hook(&self, hook_data: &HookData) -> Result<Vec<u8>> {
+            let mut bytes = vec![self.marker];
+            bytes.extend(hook_data.var_mem.addr.to_le_bytes());
+            Ok(bytes)
+        }
+    }
+
+    /// Builds a hook on `inject`, a buffer in this process standing in for the inject point.
+    fn current_process_hook(marker: u8, inject: &[u8]) -> std::sync::Arc<Hook> {
+        let handle = crate::process::open_process(std::process::id()).unwrap();
+        let target = HookTarget::Address(inject.as_ptr() as usize);
+
+        Hook::from_parts(handle, AddressHook { marker }, 0, target).unwrap()
+    }
+
+    fn read_current_process(hook: &Hook, addr: usize, size: usize) -> Vec<u8> {
+        read_bytes(&hook.handle, addr, size, Some(Duration::from_secs(1))).unwrap()
+    }
+
+    #[test]
+    fn grow_var_mem_current_process_keeps_contents() {
+        let inject = [0xCCu8; 0x10];
+        let hook = current_process_hook(0xAA, &inject);
+        let contents: Vec<u8> = (1..=0x10).collect();
+
+        let old_addr = hook.data.read().var_mem.addr;
+        write_bytes(&hook.handle, old_addr, &contents, Some(Duration::from_secs(1))).unwrap();
+
+        hook.grow_var_mem(0x40).unwrap();
+
+        let data = hook.data.read();
+        assert_ne!(data.var_mem.addr, old_addr);
+        assert_eq!(data.var_mem.size, 0x40);
+        assert_eq!(data.var_size, 0x40);
+        assert_eq!(read_current_process(&hook, data.var_mem.addr, 0x10), contents);
+    }
+
+    #[test]
+    fn grow_var_mem_current_process_rebuilds_installed_hook() {
+        let inject = [0xCCu8; 0x10];
+        let hook = current_process_hook(0xAA, &inject);
+        hook.hook(Duration::from_secs(1)).unwrap();
+
+        let old_addr = hook.data.read().var_mem.addr;
+        hook.grow_var_mem(0x40).unwrap();
+
+        let data = hook.data.read();
+        assert_ne!(data.var_mem.addr, old_addr);
+
+        let mut expected = vec![0xAA];
+        expected.extend(data.var_mem.addr.to_le_bytes());
+        assert_eq!(read_current_process(&hook, data.hook_mem.addr, expected.len()), expected);
+
+        // The jump still enters hook_mem, and the checksum covers the rewritten code.
+        let jump = data.hook_mem.addr.to_le_bytes();
+        assert_eq!(read_current_process(&hook, inject.as_ptr() as usize, jump.len()), jump);
+        drop(data);
+        assert_eq!(
+            hook.data.read().hook_mem_checksum,
+            Some(hook.hook_mem_checksum(None).unwrap())
+        );
+    }
+
+    #[test]
+    fn replace_impl_current_process_installs_new_hook() {
+        let inject = [0xCCu8; 0x10];
+        let hook = current_process_hook(0xAA, &inject);
+        hook.hook(Duration::from_secs(1)).unwrap();
+
+        hook.replace_impl(Box::new(AddressHook { marker: 0xBB }), Duration::from_secs(1))
+            .unwrap();
+
+        let data = hook.data.read();
+        let mut expected = vec![0xBB];
+        expected.extend(data.var_mem.addr.to_le_bytes());
+        assert_eq!(read_current_process(&hook, data.hook_mem.addr, expected.len()), expected);
+
+        let jump = data.hook_mem.addr.to_le_bytes();
+        assert_eq!(read_current_process(&hook, inject.as_ptr() as usize, jump.len()), jump);
+    }
+
+    #[test]
+    fn replace_impl_current_process_unhooked_is_used_on_hook() {
+        let inject = [0xCCu8; 0x10];
+        let hook = current_process_hook(0xAA, &inject);
+
+        hook.replace_impl(Box::new(AddressHook { marker: 0xBB }), Duration::from_secs(1))
+            .unwrap();
+        hook.hook(Duration::from_secs(1)).unwrap();
+
+        let data = hook.data.read();
+        assert_eq!(read_current_process(&hook, data.hook_mem.addr, 1), [0xBB]);
+    }
+}
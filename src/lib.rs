@@ -3,7 +3,10 @@ pub mod async_ext;
 pub mod asm;
 pub mod hooks;
 pub mod memory;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod process;
+pub mod tools;
 pub mod error;
 
 pub use error::{MemOpError, MemOpResult, MemOpResultExt};
\ No newline at end of file
@@ -24,7 +24,7 @@ pub mod write;
 /// let value: i32 = read::<i32>(&hook, &context, None).await?;
 /// println!("Read value: {}", value);
 /// ```
-pub async fn read<T: crate::memory::transmute::ZholTyped<T> + Send + Sync>(
+pub async fn read<T: crate::memory::transmute::ZholTyped<T> + Send + Sync + 'static>(
     hook: &crate::hooks::async_ext::AsyncZholHook,
     context: &crate::memory::MemOpContext,
     base_opt: Option<usize>,
@@ -34,14 +34,22 @@ pub async fn read<T: crate::memory::transmute::ZholTyped<T> + Send + Sync>(
         Some(b) => b,
         None => data.var_mem.addr,
     };
-    let ptr: usize = match context.at_pointer {
-        true => crate::memory::async_ext::read::read_value::<i32>(hook, base, context.timeout).await? as usize,
+    let timeout = data.timeouts.read(context.timeout);
+    let mut ptr: usize = match context.at_pointer {
+        true => crate::memory::async_ext::read::read_pointer(hook, base, context.pointer_width, timeout).await?,
         false => base,
     };
 
+    for (step, offset) in context.pointer_path.iter().enumerate() {
+        let addr = crate::memory::add_path_offset(ptr, *offset, Some(step))?;
+        ptr = crate::memory::async_ext::read::read_pointer(hook, addr, context.pointer_width, timeout)
+            .await?;
+    }
+
     drop(data); // We don't want to keep data anymore in the event of read_value::<T>() hanging. -S
 
-    crate::memory::async_ext::read::read_value::<T>(hook, ptr + context.offset, context.timeout).await
+    let addr = crate::memory::add_path_offset(ptr, context.offset, None)?;
+    crate::memory::async_ext::read::read_value::<T>(hook, addr, timeout).await
 }
 
 #[cfg(feature = "async")]
@@ -71,7 +79,7 @@ pub async fn read<T: crate::memory::transmute::ZholTyped<T> + Send + Sync>(
 /// let read_back: i32 = read::<i32>(&hook, &context, None).await?;
 /// assert_eq!(read_back, new_value);
 /// ```
-pub async fn write<T: crate::memory::transmute::ZholTyped<T> + Send + Sync>(
+pub async fn write<T: crate::memory::transmute::ZholTyped<T> + Send + Sync + 'static>(
     hook: &crate::hooks::async_ext::AsyncZholHook,
     value: T,
     context: &crate::memory::MemOpContext,
@@ -82,15 +90,26 @@ pub async fn write<T: crate::memory::transmute::ZholTyped<T> + Send + Sync>(
         Some(b) => b,
         None => data.var_mem.addr,
     };
-    let ptr: usize = match context.at_pointer {
+    let (read_timeout, write_timeout) = (
+        data.timeouts.read(context.timeout),
+        data.timeouts.write(context.timeout),
+    );
+    let mut ptr: usize = match context.at_pointer {
         true => {
-            crate::memory::async_ext::read::read_value::<i32>(hook, base, context.timeout).await?
-                as usize
+            crate::memory::async_ext::read::read_pointer(hook, base, context.pointer_width, read_timeout)
+                .await?
         }
         false => base,
     };
 
+    for (step, offset) in context.pointer_path.iter().enumerate() {
+        let addr = crate::memory::add_path_offset(ptr, *offset, Some(step))?;
+        ptr = crate::memory::async_ext::read::read_pointer(hook, addr, context.pointer_width, read_timeout)
+            .await?;
+    }
+
     drop(data);
 
-    crate::memory::async_ext::write::write_value(hook, ptr + context.offset, value, context.timeout).await
+    let addr = crate::memory::add_path_offset(ptr, context.offset, None)?;
+    crate::memory::async_ext::write::write_value(hook, addr, value, write_timeout).await
 }
\ No newline at end of file
@@ -18,7 +18,7 @@ pub async fn read_bytes(
 
 #[cfg(feature = "async")]
 /// Runs zhol::memory::read::read_value::<T>() on the local thread pool to provide an async version.
-pub async fn read_value<T: crate::memory::transmute::ZholTyped<T> + Send + Sync>(
+pub async fn read_value<T: crate::memory::transmute::ZholTyped<T> + Send + Sync + 'static>(
     hook: &crate::hooks::async_ext::AsyncZholHook,
     address: usize,
     timeout: Option<std::time::Duration>,
@@ -26,4 +26,30 @@ pub async fn read_value<T: crate::memory::transmute::ZholTyped<T> + Send + Sync>
     await_memop!(to_hook_ops(hook), |h| -> MemOpResult<T> {
         crate::memory::read::read_value::<T>(&h, address, timeout)
     })
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "async")]
+/// Runs zhol::memory::read::read_pointer() on the local thread pool to provide an async version.
+pub async fn read_pointer(
+    hook: &crate::hooks::async_ext::AsyncZholHook,
+    address: usize,
+    width: crate::memory::read::PointerWidth,
+    timeout: Option<std::time::Duration>,
+) -> MemOpResult<usize> {
+    await_memop!(to_hook_ops(hook), |h| -> MemOpResult<usize> {
+        crate::memory::read::read_pointer(&h, address, width, timeout)
+    })
+}
+
+#[cfg(feature = "async")]
+/// Runs zhol::memory::read::read_array::<T>() on the local thread pool to provide an async version.
+pub async fn read_array<T: crate::memory::transmute::ZholTyped<T> + Send + Sync + 'static>(
+    hook: &crate::hooks::async_ext::AsyncZholHook,
+    address: usize,
+    count: usize,
+    timeout: Option<std::time::Duration>,
+) -> MemOpResult<Vec<T>> {
+    await_memop!(to_hook_ops(hook), |h| -> MemOpResult<Vec<T>> {
+        crate::memory::read::read_array::<T>(&h, address, count, timeout)
+    })
+}
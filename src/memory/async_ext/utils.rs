@@ -2,6 +2,7 @@ use std::time::Duration;
 
 use windows::Win32::System::Memory::PAGE_PROTECTION_FLAGS;
 
+use crate::memory::utils::CancellationToken;
 use crate::process::SafeHandle;
 use crate::{await_memop, MemOpResult};
 
@@ -11,11 +12,12 @@ pub async fn wait_for_safe_mem(
     address: usize,
     timeout: Option<std::time::Duration>,
     needs_write: bool,
+    cancel: Option<CancellationToken>,
 ) -> MemOpResult<()> {
     use crate::await_memop;
 
     await_memop!(handle, |h| -> MemOpResult<()> {
-        crate::memory::utils::wait_for_safe_mem(&h, address, timeout, needs_write)
+        crate::memory::utils::wait_for_safe_mem(&h, address, timeout, needs_write, cancel.as_ref())
     })
 }
 
@@ -19,7 +19,7 @@ pub async fn write_bytes(
 }
 
 #[cfg(feature = "async")]
-pub async fn write_value<T: crate::memory::transmute::ZholTyped<T> + Send + Sync>(
+pub async fn write_value<T: crate::memory::transmute::ZholTyped<T> + Send + Sync + 'static>(
     hook: &AsyncZholHook,
     address: usize,
     value: T,
@@ -32,3 +32,19 @@ pub async fn write_value<T: crate::memory::transmute::ZholTyped<T> + Send + Sync
         crate::memory::write::write_value(&h, address, value, timeout)
     })
 }
+
+#[cfg(feature = "async")]
+/// Runs zhol::memory::write::write_array::<T>() on the local thread pool to provide an async version.
+pub async fn write_array<T: crate::memory::transmute::ZholTyped<T> + Clone + Send + Sync + 'static>(
+    hook: &AsyncZholHook,
+    address: usize,
+    values: &[T],
+    timeout: Option<Duration>,
+) -> MemOpResult<()> {
+    use crate::hooks::async_ext::to_hook_ops;
+
+    let values = values.to_vec();
+    await_memop!(to_hook_ops(hook), |h| -> MemOpResult<()> {
+        crate::memory::write::write_array(&h, address, &values, timeout)
+    })
+}
@@ -1,31 +1,53 @@
 #[cfg(feature = "async")]
 pub mod async_ext;
 pub mod read;
+pub mod remote;
+pub mod snapshot;
+pub mod protection;
+pub mod timeout;
 pub mod transmute;
 pub mod utils;
 pub mod write;
 
 use crate::error::IntoMemOpResult;
-use crate::memory::utils::allocate_memory;
+use crate::memory::read::{read_pointer, PointerWidth};
+use crate::memory::utils::{allocate_memory, commit_memory};
 use core::ffi::c_void;
 
 use crate::process::SafeHandle;
-use crate::{with_handle, MemOpResult};
+use crate::{memop_err, with_handle, MemOpResult};
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use windows::Win32::System::Diagnostics::Debug::WriteProcessMemory;
-use windows::Win32::System::Memory::{VirtualFree, MEM_RELEASE};
+use windows::Win32::System::Memory::{VirtualFreeEx, MEM_RELEASE};
 
 pub type Byte = Option<u8>;
 
 /// Manages a region of memory allocated in a remote process.
-/// The memory is automatically freed when the `MemoryRegion` is dropped.
+/// The memory is automatically freed when the last clone of the `MemoryRegion` is dropped.
 #[derive(Clone)]
 pub struct MemoryRegion {
     pub handle: SafeHandle,
     pub addr: usize,
     pub size: usize,
+    _allocation: Arc<RegionAllocation>,
+}
+
+/// The remote allocation behind a `MemoryRegion`, shared between its clones and freed once on drop.
+struct RegionAllocation {
+    handle: SafeHandle,
+    addr: usize,
+}
+
+impl Drop for RegionAllocation {
+    fn drop(&mut self) {
+        // Leaking the region beats blocking a drop indefinitely on a busy (or already held) handle.
+        if let Some(guard) = self.handle.acquire_with_timeout(Some(Duration::from_secs(1))) {
+            _ = unsafe { VirtualFreeEx(*guard, self.addr as *mut c_void, 0, MEM_RELEASE) };
+        }
+    }
 }
 
 impl MemoryRegion {
@@ -33,6 +55,29 @@ impl MemoryRegion {
         allocate_memory(&handle, size)
     }
 
+    /// Takes ownership of a region allocated with `VirtualAllocEx`, freeing it once the last clone is dropped.
+    pub(crate) fn from_allocation(handle: &SafeHandle, addr: usize, size: usize) -> Self {
+        MemoryRegion {
+            handle: handle.clone(),
+            addr,
+            size,
+            _allocation: Arc::new(RegionAllocation {
+                handle: handle.clone(),
+                addr,
+            }),
+        }
+    }
+
+    /// Returns a region covering `size` bytes at `offset` of this one, sharing (and keeping alive) its allocation.
+    pub(crate) fn subregion(&self, offset: usize, size: usize) -> Self {
+        MemoryRegion {
+            handle: self.handle.clone(),
+            addr: self.addr + offset,
+            size,
+            _allocation: self._allocation.clone(),
+        }
+    }
+
     /// Zeroes out the memory region. Useful for "resetting" memory to the state prior to allocation.
     pub fn zero(&self) -> MemOpResult<()> {
         let buffer = vec![0u8; self.size]; // Create a buffer of zeros with the desired size
@@ -54,25 +99,133 @@ impl MemoryRegion {
 
         Ok(())
     }
+
+    /// Commits a sub-range of a region created by `reserve_memory()`.
+    ///
+    /// # Arguments
+    /// * `offset` - Offset of the sub-range from the start of the region
+    /// * `size` - Size of the sub-range, every page it touches is committed
+    pub fn commit(&self, offset: usize, size: usize) -> MemOpResult<()> {
+        if offset.checked_add(size).is_none_or(|end| end > self.size) {
+            return Err(memop_err!(
+                "Commit of \"{size:#X}\" bytes at offset \"{offset:#X}\" exceeds region size \"{:#X}\".",
+                self.size
+            ));
+        }
+
+        commit_memory(&self.handle, self.addr + offset, size, Some(Duration::from_secs(1)))
+    }
+
+    /// Returns the absolute address of `count` elements of `T` at `offset`, erroring if they don't fit in the region.
+    fn slice_addr<T>(&self, offset: usize, count: usize) -> MemOpResult<usize> {
+        let len = count.checked_mul(std::mem::size_of::<T>());
+        match len.and_then(|len| offset.checked_add(len)) {
+            Some(end) if end <= self.size => Ok(self.addr + offset),
+            _ => Err(memop_err!(
+                "Slice of \"{count}\" \"{}\" at offset \"{offset:#X}\" exceeds region size \"{:#X}\".",
+                std::any::type_name::<T>(),
+                self.size
+            )),
+        }
+    }
+
+    /// Reads `count` contiguous elements of `T` at `offset` from the start of the region.
+    ///
+    /// Errors without reading if the elements would extend past the region.
+    pub fn read_slice<T: bytemuck::Pod>(
+        &self,
+        offset: usize,
+        count: usize,
+        timeout: Option<Duration>,
+    ) -> MemOpResult<Vec<T>> {
+        let addr = self.slice_addr::<T>(offset, count)?;
+        let bytes = read::read_bytes(&self.handle, addr, count * std::mem::size_of::<T>(), timeout)?;
+
+        bytes
+            .chunks_exact(std::mem::size_of::<T>().max(1))
+            .map(|chunk| {
+                bytemuck::try_pod_read_unaligned::<T>(chunk).map_err(|e| {
+                    memop_err!(
+                        "Failed to convert bytes to \"{}\": {e}",
+                        std::any::type_name::<T>()
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Writes a slice of `T` at `offset` from the start of the region.
+    ///
+    /// Errors without writing if the slice would extend past the region.
+    pub fn write_slice<T: bytemuck::Pod>(
+        &self,
+        offset: usize,
+        values: &[T],
+        timeout: Option<Duration>,
+    ) -> MemOpResult<()> {
+        let addr = self.slice_addr::<T>(offset, values.len())?;
+
+        write::write_bytes(&self.handle, addr, bytemuck::cast_slice(values), timeout)
+    }
 }
 
 unsafe impl Send for MemoryRegion {}
 unsafe impl Sync for MemoryRegion {}
 
-impl Drop for MemoryRegion {
-    fn drop(&mut self) {
-        _ = unsafe { VirtualFree(self.addr as *mut c_void, self.size, MEM_RELEASE) };
-    }
+/// Reads a `Pod` value at an absolute address, needing nothing but a handle.
+///
+/// The simplest entry point, bypassing `MemOpContext` and hooks entirely.
+///
+/// # Example
+/// ```rust,norun
+/// let health = zhol::memory::read_abs::<i32>(&handle, 0xDEADBEEF, None)?;
+/// ```
+pub fn read_abs<T: bytemuck::Pod>(
+    handle: &SafeHandle,
+    addr: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<T> {
+    let bytes = read::read_bytes(handle, addr, std::mem::size_of::<T>(), timeout)?;
+
+    bytemuck::try_pod_read_unaligned::<T>(&bytes).map_err(|e| {
+        memop_err!(
+            "Failed to convert bytes to \"{}\": {e}",
+            std::any::type_name::<T>()
+        )
+    })
 }
 
+/// Writes a `Pod` value at an absolute address, needing nothing but a handle.
+///
+/// # Example
+/// ```rust,norun
+/// zhol::memory::write_abs(&handle, 0xDEADBEEF, 100i32, None)?;
+/// ```
+pub fn write_abs<T: bytemuck::Pod>(
+    handle: &SafeHandle,
+    addr: usize,
+    value: T,
+    timeout: Option<Duration>,
+) -> MemOpResult<()> {
+    write::write_bytes(handle, addr, bytemuck::bytes_of(&value), timeout)
+}
+
+
 /// Context for memory operations.
 /// 
 /// This struct is used to encapsulate the parameters needed for various memory operations.
+/// 
+/// A context can optionally be bound to a hook with `with_hook()`, letting it issue reads/writes itself.
 pub struct MemOpContext {
     pub addr: usize,
     pub offset: usize,
     pub at_pointer: bool,
     pub timeout: Option<Duration>,
+    pub hook: Option<crate::hooks::ZholHook>,
+    /// Offsets of intermediate pointers followed before `offset` is applied, like a CE pointer path.
+    pub pointer_path: Vec<usize>,
+    /// Width of the pointers dereferenced by `at_pointer` and `pointer_path`, 32-bit by default.
+    pub pointer_width: PointerWidth,
 }
 
 impl MemOpContext {
@@ -82,43 +235,173 @@ impl MemOpContext {
             offset,
             at_pointer,
             timeout,
+            hook: None,
+            pointer_path: Vec::new(),
+            pointer_width: PointerWidth::U32,
         }
     }
+
+    /// Sets a CE-style pointer path: a pointer is read at each offset but the last,
+    /// and the value itself at the last offset.
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// // [[base + 0x10] + 0x20] + 0x8
+    /// let context = MemOpContext::new(base, 0x0, false, None).with_pointer_path(&[0x10, 0x20, 0x8]);
+    /// ```
+    pub fn with_pointer_path(mut self, offsets: &[usize]) -> Self {
+        match offsets.split_last() {
+            Some((last, path)) => {
+                self.pointer_path = path.to_vec();
+                self.offset = *last;
+            }
+            None => self.pointer_path.clear(),
+        }
+        self
+    }
+
+    /// Sets the width of the pointers dereferenced while resolving the address, e.g. `PointerWidth::U64` for 64-bit targets.
+    pub fn with_pointer_width(mut self, width: PointerWidth) -> Self {
+        self.pointer_width = width;
+        self
+    }
+
+    /// Binds the context to a hook, so `read()`/`write()` can be called on the context directly.
+    pub fn with_hook(mut self, hook: &crate::hooks::ZholHook) -> Self {
+        self.hook = Some(hook.clone());
+        self
+    }
+
+    fn bound_hook(&self) -> MemOpResult<&crate::hooks::ZholHook> {
+        self.hook.as_ref().ok_or(crate::memop_err!(
+            "MemOpContext is not bound to a hook, use with_hook() first."
+        ))
+    }
+
+    /// Reads a value using the hook bound to this context. See `zhol::memory::read::<T>()`.
+    pub fn read<T: crate::memory::transmute::ZholTyped<T>>(&self) -> MemOpResult<T> {
+        read::<T>(self.bound_hook()?, self)
+    }
+
+    /// Writes a value using the hook bound to this context. See `zhol::memory::write::<T>()`.
+    pub fn write<T: crate::memory::transmute::ZholTyped<T>>(&self, value: T) -> MemOpResult<()> {
+        write::<T>(self.bound_hook()?, value, self)
+    }
 }
 
 
-/// Top-level read function.
+/// Resolves the address a context points at, dereferencing the base if `at_pointer` is set
+/// and then following its `pointer_path`.
 ///
-/// Use this for reading values directly out of memory.
-/// Value must implement bytemuck::Pod.
-pub fn read<T: crate::memory::transmute::ZholTyped<T>>(hook: &crate::hooks::ZholHook, context: &MemOpContext) -> MemOpResult<T> {
+/// The base is the hook's var_mem address.
+pub fn resolve_address(hook: &crate::hooks::ZholHook, context: &MemOpContext) -> MemOpResult<usize> {
+    resolve_address_with_base(hook, context, None)
+}
+
+/// Same as `resolve_address()`, but the base is `base_opt` if given.
+pub fn resolve_address_with_base(
+    hook: &crate::hooks::ZholHook,
+    context: &MemOpContext,
+    base_opt: Option<usize>,
+) -> MemOpResult<usize> {
     let data = hook.data().read();
-    let ptr: usize = match context.at_pointer {
-        true => crate::memory::read::read_value::<i32>(&hook, data.var_mem.addr, context.timeout)? as usize,
-        false => data.var_mem.addr,
+    let base = match base_opt {
+        Some(b) => b,
+        None => data.var_mem.addr,
     };
     drop(data);
 
-    crate::memory::read::read_value::<T>(&hook, ptr + context.offset, context.timeout)
+    let timeout = hook.timeouts().read(context.timeout);
+    let mut ptr: usize = match context.at_pointer {
+        true => read_pointer(hook, base, context.pointer_width, timeout)?,
+        false => base,
+    };
+
+    for (step, offset) in context.pointer_path.iter().enumerate() {
+        let addr = add_path_offset(ptr, *offset, Some(step))?;
+        ptr = read_pointer(hook, addr, context.pointer_width, timeout)?;
+    }
+
+    add_path_offset(ptr, context.offset, None)
+}
+
+/// Adds an offset to a pointer while resolving a context, naming the pointer path step
+/// (or the final offset, for `None`) if it overflows.
+pub(crate) fn add_path_offset(ptr: usize, offset: usize, step: Option<usize>) -> MemOpResult<usize> {
+    ptr.checked_add(offset).ok_or_else(|| match step {
+        Some(step) => {
+            memop_err!("Pointer path step {step} overflows: \"{ptr:#X}\" + \"{offset:#X}\".")
+        }
+        None => memop_err!("Final offset overflows: \"{ptr:#X}\" + \"{offset:#X}\"."),
+    })
+}
+
+/// Top-level read function.
+///
+/// Use this for reading values directly out of memory.
+/// Value must implement ZholTyped (its in-memory representation must be bytemuck::Pod).
+/// Without a timeout in `context`, the hook's `TimeoutPolicy` read timeout is used.
+pub fn read<T: crate::memory::transmute::ZholTyped<T>>(
+    hook: &crate::hooks::ZholHook,
+    context: &MemOpContext,
+) -> MemOpResult<T> {
+    read_with_base::<T>(hook, context, None)
+}
+
+/// Same as `read::<T>()`, but `base_opt` overrides the hook's var_mem address as the base,
+/// matching `zhol::memory::async_ext::read::<T>()`.
+pub fn read_with_base<T: crate::memory::transmute::ZholTyped<T>>(
+    hook: &crate::hooks::ZholHook,
+    context: &MemOpContext,
+    base_opt: Option<usize>,
+) -> MemOpResult<T> {
+    let addr = resolve_address_with_base(hook, context, base_opt)?;
+
+    let timeout = hook.timeouts().read(context.timeout);
+    crate::memory::read::read_value::<T>(&hook, addr, timeout)
 }
 
 
 /// Top-level write function.
 ///
 /// Use this for writing types directly to memory.
-/// Value must implement bytemuck::Pod.
+/// Value must implement ZholTyped (its in-memory representation must be bytemuck::Pod).
+/// Without a timeout in `context`, the hook's `TimeoutPolicy` write timeout is used.
 pub fn write<T: crate::memory::transmute::ZholTyped<T>>(
     hook: &crate::hooks::ZholHook,
     value: T,
     context: &MemOpContext,
 ) -> MemOpResult<()> {
-    let data = hook.data().read();
-    let ptr: usize = match context.at_pointer {
-        true => crate::memory::read::read_value::<i32>(&hook, data.var_mem.addr, context.timeout)? as usize,
-        false => data.var_mem.addr,
-    };
+    write_with_base::<T>(hook, value, context, None)
+}
 
-    drop(data);
+/// Same as `write::<T>()`, but `base_opt` overrides the hook's var_mem address as the base,
+/// matching `zhol::memory::async_ext::write::<T>()`.
+pub fn write_with_base<T: crate::memory::transmute::ZholTyped<T>>(
+    hook: &crate::hooks::ZholHook,
+    value: T,
+    context: &MemOpContext,
+    base_opt: Option<usize>,
+) -> MemOpResult<()> {
+    let addr = resolve_address_with_base(hook, context, base_opt)?;
+
+    let timeout = hook.timeouts().write(context.timeout);
+    crate::memory::write::write_value::<T>(&hook, addr, value, timeout)
+}
 
-    crate::memory::write::write_value::<T>(&hook, ptr + context.offset, value, context.timeout)
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_path_offset_names_the_overflowing_step() {
+        assert_eq!(add_path_offset(0x1000, 0x10, Some(0)).unwrap(), 0x1010);
+        assert_eq!(add_path_offset(0x1000, 0x10, None).unwrap(), 0x1010);
+
+        let step = add_path_offset(usize::MAX, 0x10, Some(2)).unwrap_err();
+        assert!(step.to_string().contains("step 2"), "{step}");
+
+        let last = add_path_offset(usize::MAX - 0xF, 0x10, None).unwrap_err();
+        assert!(last.to_string().contains("Final offset"), "{last}");
+    }
+}
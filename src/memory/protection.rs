@@ -0,0 +1,111 @@
+pub use windows::Win32::System::Memory::{
+    PAGE_EXECUTE, PAGE_EXECUTE_READ, PAGE_EXECUTE_READWRITE, PAGE_EXECUTE_WRITECOPY, PAGE_GUARD,
+    PAGE_NOACCESS, PAGE_PROTECTION_FLAGS, PAGE_READONLY, PAGE_READWRITE, PAGE_WRITECOPY,
+};
+
+/// Builders for the common page protections, for use with e.g. `change_memory_protection()`.
+///
+/// # Example
+/// ```rust,norun
+/// let old = change_memory_protection(&handle, addr, 0x10, None, Protection::rwx())?;
+/// ```
+pub struct Protection;
+
+impl Protection {
+    /// `PAGE_EXECUTE_READWRITE`
+    pub const fn rwx() -> PAGE_PROTECTION_FLAGS {
+        PAGE_EXECUTE_READWRITE
+    }
+
+    /// `PAGE_READWRITE`
+    pub const fn rw() -> PAGE_PROTECTION_FLAGS {
+        PAGE_READWRITE
+    }
+
+    /// `PAGE_EXECUTE_READ`
+    pub const fn rx() -> PAGE_PROTECTION_FLAGS {
+        PAGE_EXECUTE_READ
+    }
+
+    /// `PAGE_READONLY`
+    pub const fn ro() -> PAGE_PROTECTION_FLAGS {
+        PAGE_READONLY
+    }
+
+    /// `PAGE_NOACCESS`
+    pub const fn none() -> PAGE_PROTECTION_FLAGS {
+        PAGE_NOACCESS
+    }
+
+    /// Adds `PAGE_GUARD` to a protection, e.g. `Protection::guarded(Protection::rw())`.
+    pub const fn guarded(protection: PAGE_PROTECTION_FLAGS) -> PAGE_PROTECTION_FLAGS {
+        PAGE_PROTECTION_FLAGS(protection.0 | PAGE_GUARD.0)
+    }
+
+    /// Returns whether a page with protection `actual` allows every access `required` allows.
+    ///
+    /// The base protections are values rather than flags, so they are compared by what they permit:
+    /// `PAGE_EXECUTE_READWRITE` satisfies `Protection::rx()`, while `PAGE_EXECUTE` doesn't.
+    /// Copy-on-write counts as writable. `PAGE_GUARD` has to be set on both or neither, and other
+    /// modifiers like `PAGE_NOCACHE` are ignored.
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// assert!(Protection::satisfies(PAGE_EXECUTE_READWRITE, Protection::rx()));
+    /// assert!(!Protection::satisfies(Protection::guarded(Protection::rw()), Protection::ro()));
+    /// ```
+    pub fn satisfies(actual: PAGE_PROTECTION_FLAGS, required: PAGE_PROTECTION_FLAGS) -> bool {
+        let (Some(actual_access), Some(required_access)) = (access(actual), access(required)) else {
+            return false;
+        };
+
+        (actual.0 & PAGE_GUARD.0) == (required.0 & PAGE_GUARD.0)
+            && actual_access & required_access == required_access
+    }
+}
+
+const ACCESS_READ: u8 = 0b001;
+const ACCESS_WRITE: u8 = 0b010;
+const ACCESS_EXECUTE: u8 = 0b100;
+
+/// Maps the base protection (the low byte) to the accesses it permits, `None` if it isn't exactly one.
+fn access(protection: PAGE_PROTECTION_FLAGS) -> Option<u8> {
+    match PAGE_PROTECTION_FLAGS(protection.0 & 0xFF) {
+        PAGE_NOACCESS => Some(0),
+        PAGE_READONLY => Some(ACCESS_READ),
+        PAGE_READWRITE | PAGE_WRITECOPY => Some(ACCESS_READ | ACCESS_WRITE),
+        PAGE_EXECUTE => Some(ACCESS_EXECUTE),
+        PAGE_EXECUTE_READ => Some(ACCESS_READ | ACCESS_EXECUTE),
+        PAGE_EXECUTE_READWRITE | PAGE_EXECUTE_WRITECOPY => {
+            Some(ACCESS_READ | ACCESS_WRITE | ACCESS_EXECUTE)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn satisfies_compares_permitted_access() {
+        assert!(Protection::satisfies(PAGE_EXECUTE_READWRITE, Protection::rx()));
+        assert!(Protection::satisfies(PAGE_EXECUTE_WRITECOPY, Protection::rw()));
+        assert!(Protection::satisfies(PAGE_READWRITE, Protection::ro()));
+        assert!(!Protection::satisfies(PAGE_EXECUTE, Protection::rx()));
+        assert!(!Protection::satisfies(PAGE_READWRITE, Protection::rx()));
+        // PAGE_WRITECOPY (0x08) contains no bit of PAGE_READONLY (0x02), yet permits reading.
+        assert!(Protection::satisfies(PAGE_WRITECOPY, Protection::ro()));
+    }
+
+    #[test]
+    fn satisfies_checks_guard_and_noaccess_separately() {
+        assert!(!Protection::satisfies(Protection::guarded(Protection::rw()), Protection::rw()));
+        assert!(Protection::satisfies(
+            Protection::guarded(Protection::rwx()),
+            Protection::guarded(Protection::rx())
+        ));
+        assert!(!Protection::satisfies(PAGE_NOACCESS, Protection::ro()));
+        assert!(!Protection::satisfies(PAGE_PROTECTION_FLAGS(0), Protection::none()));
+    }
+}
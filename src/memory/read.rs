@@ -1,14 +1,18 @@
-use crate::error::IntoMemOpResult;
+use crate::error::{IntoMemOpResult, INVALID_ALLOCATION_TYPE, INVALID_PROTECTION_FLAGS};
 use crate::hooks::ZholHook;
-use crate::memory::utils::wait_for_safe_mem;
-use crate::process::SafeHandle;
+use crate::memory::utils::{
+    check_cancelled, query_memory, region_permits, wait_for_safe_mem, CStr256, CancellationToken,
+};
+use crate::process::{exited_or, SafeHandle};
 use crate::with_handle;
-use crate::MemOpResult;
+use crate::{MemOpError, MemOpResult, MemOpResultExt};
 
 use anyhow::anyhow;
 use std::time::Duration;
 use windows::Win32::System::Diagnostics::Debug::ReadProcessMemory;
+use windows::Win32::System::Memory::{MEM_COMMIT, PAGE_PROTECTION_FLAGS};
 
+use super::protection::Protection;
 use super::transmute::ZholTyped;
 use super::MemOpContext;
 
@@ -19,28 +23,149 @@ pub fn read_bytes(
     addr: usize,
     size: usize,
     timeout: Option<Duration>,
+) -> MemOpResult<Vec<u8>> {
+    read_bytes_cancellable(handle, addr, size, timeout, None)
+}
+
+/// Same as `read_bytes()`, but aborts with `MemOpError::Cancelled` if `cancel` is set before any of its steps.
+pub fn read_bytes_cancellable(
+    handle: &SafeHandle,
+    addr: usize,
+    size: usize,
+    timeout: Option<Duration>,
+    cancel: Option<&CancellationToken>,
+) -> MemOpResult<Vec<u8>> {
+    wait_for_safe_mem(&handle.clone(), addr, timeout, false, cancel)?;
+    check_cancelled(cancel)?;
+    let buffer = read_process_memory(handle, addr, size, timeout)?;
+
+    wait_for_safe_mem(&handle.clone(), addr, timeout, false, cancel)?;
+
+    Ok(buffer)
+}
+
+/// Reads bytes until a terminator sequence, or until `max` bytes were read without finding one.
+///
+/// Reads are done a page at a time, so nothing past the page holding the terminator is touched.
+///
+/// # Returns
+/// Returns the bytes before the terminator, which itself is not included
+///
+/// # Example
+/// ```rust,norun
+/// // Packet terminated by 00 00
+/// let packet = read_bytes_until(&handle, packet_addr, &[0x00, 0x00], 0x1000, None)?;
+/// ```
+pub fn read_bytes_until(
+    handle: &SafeHandle,
+    addr: usize,
+    terminator: &[u8],
+    max: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<u8>> {
+    if terminator.is_empty() {
+        return Err(anyhow!("Cannot read until an empty terminator.").into());
+    }
+
+    let mut buffer: Vec<u8> = Vec::new();
+
+    while buffer.len() < max {
+        let chunk_addr = addr + buffer.len();
+        let page_remaining = 0x1000 - (chunk_addr & 0xFFF);
+        let chunk = read_bytes(handle, chunk_addr, page_remaining.min(max - buffer.len()), timeout)?;
+
+        // The terminator may straddle the previous chunk.
+        let search_start = buffer.len().saturating_sub(terminator.len() - 1);
+        buffer.extend_from_slice(&chunk);
+
+        if let Some(pos) = buffer[search_start..]
+            .windows(terminator.len())
+            .position(|window| window == terminator)
+        {
+            buffer.truncate(search_start + pos);
+            return Ok(buffer);
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// Reads a range region by region, zero-filling pages that are uncommitted or unreadable.
+///
+/// Unlike `read_bytes()`, this succeeds on ranges with gaps, such as module images with uncommitted pages.
+///
+/// # Returns
+/// Returns exactly `size` bytes
+pub fn read_committed(
+    handle: &SafeHandle,
+    addr: usize,
+    size: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<u8>> {
+    read_committed_cancellable(handle, addr, size, timeout, None)
+}
+
+/// Same as `read_committed()`, but aborts with `MemOpError::Cancelled` if `cancel` is set before any region is read.
+pub fn read_committed_cancellable(
+    handle: &SafeHandle,
+    addr: usize,
+    size: usize,
+    timeout: Option<Duration>,
+    cancel: Option<&CancellationToken>,
+) -> MemOpResult<Vec<u8>> {
+    let mut buffer = vec![0u8; size];
+    let end = addr + size;
+    let mut cursor = addr;
+
+    while cursor < end {
+        check_cancelled(cancel)?;
+        let mbi = query_memory(handle, cursor, timeout)?;
+        let region_end = (mbi.BaseAddress as usize + mbi.RegionSize).min(end);
+        if region_end <= cursor {
+            break;
+        }
+
+        if region_permits(&mbi, cursor, region_end - cursor, false) {
+            let bytes = read_process_memory(handle, cursor, region_end - cursor, timeout)?;
+            let offset = cursor - addr;
+            buffer[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        }
+
+        cursor = region_end;
+    }
+
+    Ok(buffer)
+}
+
+/// A single `ReadProcessMemory` call without any safety checks, truncated to the bytes actually read.
+fn read_process_memory(
+    handle: &SafeHandle,
+    addr: usize,
+    size: usize,
+    timeout: Option<Duration>,
 ) -> MemOpResult<Vec<u8>> {
     let mut buffer = vec![0u8; size];
     let mut bytes_read = 0;
 
-    wait_for_safe_mem(&handle.clone(), addr, timeout, false)?;
     with_handle!(&handle.clone(), timeout, |guard| -> (), {
-        unsafe {
+        let result = unsafe {
             ReadProcessMemory(
                 *guard,
                 addr as *const _,
                 buffer.as_mut_ptr() as *mut _,
                 size,
                 Some(&mut bytes_read),
-            ).into_memop_result(Some(anyhow!("ReadProcessMemory in read_bytes()")))?;
+            ).into_memop_result(Some(anyhow!("ReadProcessMemory in read_bytes()")))
+        };
 
-            std::thread::sleep(Duration::from_nanos(1));
+        std::thread::sleep(Duration::from_nanos(1));
 
-            Ok(())
-        }
-    })?;
+        result
+    })
+    .map_err(|e| exited_or(handle, e))?;
 
-    wait_for_safe_mem(&handle.clone(), addr, timeout, false)?;
+    #[cfg(feature = "metrics")]
+    crate::metrics::METRICS.record_read(bytes_read);
 
     buffer.truncate(bytes_read);
 
@@ -52,13 +177,100 @@ pub fn read_value<T: ZholTyped<T>>(
     address: usize,
     timeout: Option<Duration>,
 ) -> MemOpResult<T> {
-    // Calculate size needed for the type
-    let size = std::mem::size_of::<T>();
+    let (value, _) = read_value_raw::<T>(hook, address, timeout)?;
+
+    Ok(value)
+}
+
+/// Reads a value if its memory is safe to read right now, without waiting for it to become safe.
+///
+/// The region is checked with a single `VirtualQueryEx` call; if it is unsafe (e.g. transiently
+/// unmapped or guarded), `Ok(None)` is returned immediately. Meant for render loops that would
+/// rather skip a value for a frame than stall.
+///
+/// # Example
+/// ```rust,norun
+/// if let Some(health) = try_read_value::<f32>(&hook, health_addr)? {
+///     draw_health(health);
+/// }
+/// ```
+pub fn try_read_value<T: ZholTyped<T>>(hook: &ZholHook, address: usize) -> MemOpResult<Option<T>> {
+    let handle = hook.handle();
+    let timeout = hook.timeouts().read;
+    let size = std::mem::size_of::<T::Repr>();
+
+    let mbi = query_memory(&handle, address, timeout)?;
+    if !region_permits(&mbi, address, size, false) {
+        return Ok(None);
+    }
+
+    let raw_buffer = read_process_memory(&handle, address, size, timeout)?;
+    let context = MemOpContext::new(address, 0x0, false, timeout);
+
+    Ok(T::transmute_from(&raw_buffer, hook, &context)?)
+}
+
+/// Reads a value only if the page holding it has the required protection, e.g. to confirm it's code rather than data.
+///
+/// The protection matches if it allows every access `required` does, see `Protection::satisfies()`.
+/// A guarded page only matches if `required` is guarded too.
+/// The whole value has to lie in one region with that protection.
+///
+/// # Returns
+/// Returns the value, or `MemOpError::MemoryStateInvalid` if the memory isn't committed with the required protection
+///
+/// # Example
+/// ```rust,norun
+/// let opcode = read_value_requiring::<u32>(&hook, func_addr, Protection::rx(), None)?;
+/// ```
+pub fn read_value_requiring<T: ZholTyped<T>>(
+    hook: &ZholHook,
+    address: usize,
+    required: PAGE_PROTECTION_FLAGS,
+    timeout: Option<Duration>,
+) -> MemOpResult<T> {
+    let size = std::mem::size_of::<T::Repr>();
+    let mbi = query_memory(&hook.handle(), address, timeout)?;
+
+    let mut mem_err_flag = 0;
+    if mbi.State != MEM_COMMIT {
+        mem_err_flag |= INVALID_ALLOCATION_TYPE;
+    }
+    let region_end = (mbi.BaseAddress as usize).saturating_add(mbi.RegionSize);
+    if !Protection::satisfies(mbi.Protect, required) || address.saturating_add(size) > region_end {
+        mem_err_flag |= INVALID_PROTECTION_FLAGS;
+    }
+
+    if mem_err_flag != 0 {
+        return Err(MemOpError::MemoryStateInvalid((
+            mbi.State,
+            mbi.Protect,
+            mbi.Type,
+            mem_err_flag,
+            Some(anyhow!(
+                "read_value_requiring(): \"{address:#X}\" requires protection \"{:#X}\".",
+                required.0
+            )),
+        )));
+    }
+
+    read_value::<T>(hook, address, timeout)
+}
+
+/// Same as `read_value::<T>()`, but also returns the raw bytes the value was transmuted from.
+///
+/// Useful for debugging custom `Transmutable` implementations.
+pub fn read_value_raw<T: ZholTyped<T>>(
+    hook: &ZholHook,
+    address: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<(T, Vec<u8>)> {
+    // Calculate size needed for the in-memory representation of the type
+    let size = std::mem::size_of::<T::Repr>();
     let raw_buffer: Vec<u8> = read_bytes(&hook.handle(), address, size, timeout)?;
 
     let context = MemOpContext::new(address, 0x0, false, timeout);
 
-    // let value = T::transmute_from(&raw_buffer)?;
     let value = match T::transmute_from(&raw_buffer, hook, &context)? {
         Some(a) => a,
         None => {
@@ -70,22 +282,394 @@ pub fn read_value<T: ZholTyped<T>>(
         }
     };
 
-    Ok(value)
+    Ok((value, raw_buffer))
+}
+
+/// Width of a pointer in the target process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointerWidth {
+    /// 32-bit process (or WOW64)
+    U32,
+    /// 64-bit process
+    U64,
+}
+
+impl PointerWidth {
+    /// Size of the pointer in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            PointerWidth::U32 => 4,
+            PointerWidth::U64 => 8,
+        }
+    }
+}
+
+/// Reads a pointer of the given width, zero-extending 32-bit pointers.
+///
+/// Used for `MemOpContext` resolution, pointer chains and `read_wide_string()`. Types whose layout embeds
+/// a pointer, like `StdString`, decode it from the bytes they were read from instead.
+pub fn read_pointer(
+    hook: &ZholHook,
+    address: usize,
+    width: PointerWidth,
+    timeout: Option<Duration>,
+) -> MemOpResult<usize> {
+    let ptr = match width {
+        PointerWidth::U32 => read_value::<u32>(hook, address, timeout)? as u64,
+        PointerWidth::U64 => read_value::<u64>(hook, address, timeout)?,
+    };
+
+    usize::try_from(ptr).map_err(|_| {
+        anyhow!("Pointer \"{ptr:#X}\" at \"{address:#X}\" does not fit in usize.").into()
+    })
+}
+
+/// Reads a pointer like `read_pointer()`, but errors unless it points to committed memory.
+///
+/// Meant for following pointer chains, where an uncommitted target means a stale or wrong chain.
+///
+/// # Returns
+/// Returns the pointer, or `MemOpError::MemoryStateInvalid` naming both addresses if its target isn't committed
+///
+/// # Example
+/// ```rust,norun
+/// let entity = read_valid_pointer(&hook, entity_list + 0x8, PointerWidth::U64, None)?;
+/// ```
+pub fn read_valid_pointer(
+    hook: &ZholHook,
+    address: usize,
+    width: PointerWidth,
+    timeout: Option<Duration>,
+) -> MemOpResult<usize> {
+    let ptr = read_pointer(hook, address, width, timeout)?;
+
+    let mbi = query_memory(&hook.handle(), ptr, timeout).with_context(|| {
+        format!("read_valid_pointer(): querying \"{ptr:#X}\" read at \"{address:#X}\"")
+    })?;
+
+    if mbi.State != MEM_COMMIT {
+        return Err(MemOpError::MemoryStateInvalid((
+            mbi.State,
+            mbi.Protect,
+            mbi.Type,
+            INVALID_ALLOCATION_TYPE,
+            Some(anyhow!(
+                "Pointer \"{ptr:#X}\" read at \"{address:#X}\" doesn't point to committed memory."
+            )),
+        )));
+    }
+
+    Ok(ptr)
+}
+
+/// Reads the pointer at `ptr_addr` and then a value at that pointer plus `offset`.
+///
+/// # Example
+/// ```rust,norun
+/// // [ptr_addr] + 0x10 in a 64-bit target
+/// let health = read_via_pointer::<i32>(&hook, ptr_addr, 0x10, PointerWidth::U64, None)?;
+/// ```
+pub fn read_via_pointer<T: ZholTyped<T>>(
+    hook: &ZholHook,
+    ptr_addr: usize,
+    offset: usize,
+    width: PointerWidth,
+    timeout: Option<Duration>,
+) -> MemOpResult<T> {
+    let ptr = read_pointer(hook, ptr_addr, width, timeout)?;
+    let addr = ptr.checked_add(offset).ok_or(crate::memop_err!(
+        "Pointer \"{ptr:#X}\" read at \"{ptr_addr:#X}\" overflows with offset \"{offset:#X}\"."
+    ))?;
+
+    read_value::<T>(hook, addr, timeout)
+}
+
+/// Reads an array of `count` pointers at `base` and a value at each, skipping null entries.
+///
+/// The pointers are read at once, the values one at a time.
+///
+/// # Returns
+/// Returns a value per pointer, `None` for null pointers
+///
+/// # Example
+/// ```rust,norun
+/// // Global array of 64 entity pointers in a 32-bit target
+/// let entities = read_pointer_array::<Entity>(&hook, entity_list, 64, PointerWidth::U32, None)?;
+/// ```
+pub fn read_pointer_array<T: ZholTyped<T>>(
+    hook: &ZholHook,
+    base: usize,
+    count: usize,
+    width: PointerWidth,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<Option<T>>> {
+    let raw_buffer = read_bytes(&hook.handle(), base, count * width.size(), timeout)?;
+    if raw_buffer.len() != count * width.size() {
+        return Err(anyhow!(
+            "Only read \"{}\" of \"{}\" bytes for pointer array at \"{base:#X}\".",
+            raw_buffer.len(),
+            count * width.size()
+        )
+        .into());
+    }
+
+    raw_buffer
+        .chunks_exact(width.size())
+        .map(|chunk| {
+            let ptr = match width {
+                PointerWidth::U32 => u32::from_le_bytes(chunk.try_into().unwrap()) as u64,
+                PointerWidth::U64 => u64::from_le_bytes(chunk.try_into().unwrap()),
+            };
+
+            match ptr {
+                0 => Ok(None),
+                ptr => read_value::<T>(hook, ptr as usize, timeout).map(Some),
+            }
+        })
+        .collect()
+}
+
+/// Reads the nodes of an intrusive linked list, following the `next` pointer stored in each node.
+///
+/// Traversal stops at a null pointer, after `max` nodes, or when a node is reached a second time,
+/// so corrupted or circular lists still terminate.
+///
+/// # Arguments
+/// * `head` - Address of the first node, or null for an empty list
+/// * `next_offset` - Offset of the `next` pointer within a node
+///
+/// # Example
+/// ```rust,norun
+/// // struct Entity { ..., next: *mut Entity } with `next` at 0x48
+/// let entities = read_linked_list::<Entity>(&hook, first_entity, 0x48, PointerWidth::U64, 1024, None)?;
+/// ```
+pub fn read_linked_list<T: ZholTyped<T>>(
+    hook: &ZholHook,
+    head: usize,
+    next_offset: usize,
+    width: PointerWidth,
+    max: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<T>> {
+    let mut nodes = Vec::new();
+    let mut visited = std::collections::HashSet::new();
+    let mut node = head;
+
+    while node != 0 && nodes.len() < max && visited.insert(node) {
+        nodes.push(read_value::<T>(hook, node, timeout)?);
+        node = read_pointer(hook, node + next_offset, width, timeout)?;
+    }
+
+    Ok(nodes)
+}
+
+/// Reads a `#[repr(C)]` struct, asserting its Rust size matches the expected in-memory size first.
+///
+/// This turns silent layout drift (padding, changed fields) into an error instead of a garbage read.
+pub fn read_struct<T: bytemuck::Pod>(
+    hook: &ZholHook,
+    address: usize,
+    expected_size: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<T> {
+    let size = std::mem::size_of::<T>();
+    if size != expected_size {
+        return Err(anyhow!(
+            "Size of \"{}\" is \"{size:#X}\", but the in-memory struct is \"{expected_size:#X}\".",
+            type_name::<T>()
+        )
+        .into());
+    }
+
+    let raw_buffer = read_bytes(&hook.handle(), address, size, timeout)?;
+
+    bytemuck::try_pod_read_unaligned::<T>(&raw_buffer)
+        .map_err(|e| anyhow!("Failed to convert bytes to \"{}\": {e}", type_name::<T>()).into())
+}
+
+/// Reads a header stored `header_size` bytes before the pointer a structure hands out.
+///
+/// Common for allocator metadata and `Vec`-like layouts, where the pointer points at the data past the header.
+///
+/// # Arguments
+/// * `data_ptr` - The pointer to the data following the header
+/// * `header_size` - Distance from the start of the header to `data_ptr`, usually `size_of::<H>()`
+///
+/// # Example
+/// ```rust,norun
+/// #[repr(C)]
+/// #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+/// struct ArrayHeader {
+///     capacity: u32,
+///     len: u32,
+/// }
+///
+/// let header = read_header::<ArrayHeader>(&hook, items_ptr, 0x8, None)?;
+/// ```
+pub fn read_header<H: bytemuck::Pod>(
+    hook: &ZholHook,
+    data_ptr: usize,
+    header_size: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<H> {
+    let address = data_ptr.checked_sub(header_size).ok_or(anyhow!(
+        "Header of \"{header_size:#X}\" bytes before \"{data_ptr:#X}\" is out of the address space."
+    ))?;
+
+    let raw_buffer = read_bytes(&hook.handle(), address, std::mem::size_of::<H>(), timeout)?;
+
+    bytemuck::try_pod_read_unaligned::<H>(&raw_buffer)
+        .map_err(|e| anyhow!("Failed to convert bytes to \"{}\": {e}", type_name::<H>()).into())
+}
+
+/// Reads elements spaced `stride` bytes apart until one equals `sentinel`, or `max` elements were read.
+///
+/// The sentinel itself is not included. Elements are read one at a time, so nothing past the sentinel is touched.
+///
+/// # Example
+/// ```rust,norun
+/// // Null-terminated array of entity pointers
+/// let entities = read_until::<u32>(&hook, list_addr, 0, 256, 0x4, None)?;
+/// ```
+pub fn read_until<T: bytemuck::Pod + PartialEq>(
+    hook: &ZholHook,
+    base: usize,
+    sentinel: T,
+    max: usize,
+    stride: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<T>> {
+    let size = std::mem::size_of::<T>();
+    if stride < size {
+        return Err(anyhow!(
+            "Stride \"{stride:#X}\" is smaller than \"{}\" of size \"{size:#X}\".",
+            type_name::<T>()
+        )
+        .into());
+    }
+
+    let handle = hook.handle();
+    let mut elements = Vec::new();
+
+    for i in 0..max {
+        let raw_buffer = read_bytes(&handle, base + i * stride, size, timeout)?;
+        let element = bytemuck::try_pod_read_unaligned::<T>(&raw_buffer).map_err(|e| {
+            anyhow!("Failed to convert bytes to \"{}\": {e}", type_name::<T>())
+        })?;
+
+        if element == sentinel {
+            break;
+        }
+
+        elements.push(element);
+    }
+
+    Ok(elements)
+}
+
+/// Reads `count` contiguous values of type T with a single read.
+///
+/// Each element is transmuted from its own `size_of::<T::Repr>()` chunk of the read bytes.
+/// Decoding errors name the index and address of the failing element.
+pub fn read_array<T: ZholTyped<T>>(
+    hook: &ZholHook,
+    address: usize,
+    count: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<T>> {
+    let stride = std::mem::size_of::<T::Repr>();
+    if stride == 0 {
+        return Err(anyhow!("Cannot read an array of zero-sized \"{}\".", type_name::<T>()).into());
+    }
+
+    let raw_buffer = read_bytes(&hook.handle(), address, stride * count, timeout)?;
+    if raw_buffer.len() != stride * count {
+        return Err(anyhow!(
+            "Only read \"{}\" of \"{}\" bytes for array at \"{address:#X}\".",
+            raw_buffer.len(),
+            stride * count
+        )
+        .into());
+    }
+
+    raw_buffer
+        .chunks_exact(stride)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let element_addr = address + i * stride;
+            let context = MemOpContext::new(element_addr, 0x0, false, timeout);
+
+            let value: MemOpResult<T> = match T::transmute_from(&chunk.to_vec(), hook, &context) {
+                Ok(Some(value)) => Ok(value),
+                Ok(None) => Err(anyhow!("No data from type \"{}\"", type_name::<T>()).into()),
+                Err(e) => Err(e.into()),
+            };
+
+            value.with_context(|| {
+                format!("Failed decoding element \"{i}\" at address \"{element_addr:#X}\"")
+            })
+        })
+        // Stops at the first element that fails to decode.
+        .collect()
+}
+
+/// Reads a table of `count` contiguous `CStr256` records, returning each as a `String`.
+pub fn read_c_string_table(
+    hook: &ZholHook,
+    base: usize,
+    count: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<String>> {
+    let table = read_array::<CStr256>(hook, base, count, timeout)?;
+
+    Ok(table.iter().map(|entry| entry.as_str().to_string()).collect())
 }
 
 /// Top-level read function.
 ///
 /// Use this for reading values directly out of game memory.
-/// Value must implement bytemuck::Pod.
+/// Value must implement ZholTyped (its in-memory representation must be bytemuck::Pod).
 pub fn read<T: ZholTyped<T>>(hook: &ZholHook, context: &MemOpContext) -> MemOpResult<T> {
-    let data = hook.data().read();
-    let ptr: usize = match context.at_pointer {
-        true => read_value::<i32>(&hook, data.var_mem.addr, context.timeout)? as usize,
-        false => data.var_mem.addr,
+    crate::memory::read::<T>(hook, context)
+}
+
+/// Returns the amount of bytes spanned by a bitfield, and the mask of its value.
+pub(crate) fn bitfield_span(bit_offset: u32, bit_width: u32) -> MemOpResult<(usize, u64)> {
+    let end = bit_offset.checked_add(bit_width);
+    if bit_width == 0 || end.is_none_or(|end| end > 64) {
+        return Err(anyhow!(
+            "Bitfield of width \"{bit_width}\" at bit \"{bit_offset}\" does not fit in 64 bits."
+        )
+        .into());
+    }
+
+    let mask = match bit_width {
+        64 => u64::MAX,
+        w => (1u64 << w) - 1,
     };
-    drop(data);
 
-    read_value::<T>(&hook, ptr + context.offset, context.timeout)
+    Ok(((bit_offset + bit_width).div_ceil(8) as usize, mask))
+}
+
+/// Reads `bit_width` bits starting at `bit_offset` of the (little-endian) integer a context points at.
+///
+/// Only the bytes spanned by the bitfield are read. `timeout` falls back to the context's, then the hook's policy.
+pub fn read_bitfield(
+    hook: &ZholHook,
+    context: &MemOpContext,
+    bit_offset: u32,
+    bit_width: u32,
+    timeout: Option<Duration>,
+) -> MemOpResult<u64> {
+    let (size, mask) = bitfield_span(bit_offset, bit_width)?;
+    let addr = crate::memory::resolve_address(hook, context)?;
+    let timeout = hook.timeouts().read(timeout.or(context.timeout));
+
+    let mut raw = [0u8; 8];
+    let bytes = read_bytes(&hook.handle(), addr, size, timeout)?;
+    raw[..bytes.len()].copy_from_slice(&bytes);
+
+    Ok((u64::from_le_bytes(raw) >> bit_offset) & mask)
 }
 
 pub fn read_wide_string(hook: &ZholHook, address: usize) -> String {
@@ -98,8 +682,7 @@ pub fn read_wide_string(hook: &ZholHook, address: usize) -> String {
 
     // Inline vs heap-pointer distinction
     let string_address = if byte_len >= 8 {
-        let ptr: u32 = read_value::<u32>(hook, address, Some(Duration::from_secs(5))).unwrap();
-        ptr as usize
+        read_pointer(hook, address, PointerWidth::U32, Some(Duration::from_secs(5))).unwrap()
     } else {
         address
     };
@@ -119,3 +702,23 @@ pub fn read_wide_string(hook: &ZholHook, address: usize) -> String {
 
     String::from_utf16(&utf16).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::bitfield_span;
+
+    #[test]
+    fn bitfield_span_covers_spanned_bytes() {
+        assert_eq!(bitfield_span(4, 4).unwrap(), (1, 0xF));
+        assert_eq!(bitfield_span(6, 4).unwrap(), (2, 0xF));
+        assert_eq!(bitfield_span(0, 64).unwrap(), (8, u64::MAX));
+    }
+
+    #[test]
+    fn bitfield_span_rejects_invalid_fields() {
+        assert!(bitfield_span(0, 0).is_err());
+        assert!(bitfield_span(60, 5).is_err());
+        assert!(bitfield_span(u32::MAX, 1).is_err());
+        assert!(bitfield_span(1, u32::MAX).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::hooks::ZholHook;
+use crate::memory::read::read_value;
+use crate::memory::transmute::ZholTyped;
+use crate::memory::write::write_value;
+use crate::MemOpResult;
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// A typed address in process memory.
+///
+/// This only stores the address; every read/write goes to the process.
+pub struct RemotePtr<T> {
+    pub addr: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for RemotePtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RemotePtr<T> {}
+
+impl<T> std::fmt::Debug for RemotePtr<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RemotePtr<{}>({:#X})", std::any::type_name::<T>(), self.addr)
+    }
+}
+
+impl<T> RemotePtr<T> {
+    pub fn new(addr: usize) -> Self {
+        RemotePtr {
+            addr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ZholTyped<T>> RemotePtr<T> {
+    /// Reads the value this pointer points at.
+    pub fn read(&self, hook: &ZholHook, timeout: Option<Duration>) -> MemOpResult<T> {
+        read_value::<T>(hook, self.addr, timeout)
+    }
+
+    /// Writes a value to the address this pointer points at.
+    pub fn write(&self, hook: &ZholHook, value: T, timeout: Option<Duration>) -> MemOpResult<()> {
+        write_value::<T>(hook, self.addr, value, timeout)
+    }
+}
+
+/// A remote value paired with a local shadow copy of what was last written (or refreshed).
+///
+/// Useful for UIs that display and edit values, as `get()` never touches process memory.
+pub struct ShadowValue<T> {
+    pub ptr: RemotePtr<T>,
+    pub timeout: Option<Duration>,
+    shadow: Option<T>,
+}
+
+impl<T: ZholTyped<T> + Clone> ShadowValue<T> {
+    pub fn new(ptr: RemotePtr<T>, timeout: Option<Duration>) -> Self {
+        ShadowValue {
+            ptr,
+            timeout,
+            shadow: None,
+        }
+    }
+
+    /// Writes a value to process memory, updating the shadow only once the write succeeded.
+    pub fn set(&mut self, hook: &ZholHook, value: T) -> MemOpResult<()> {
+        self.ptr.write(hook, value.clone(), self.timeout)?;
+        self.shadow = Some(value);
+
+        Ok(())
+    }
+
+    /// Returns the shadow copy without reading process memory, `None` if nothing was written or refreshed yet.
+    pub fn get(&self) -> Option<&T> {
+        self.shadow.as_ref()
+    }
+
+    /// Re-syncs the shadow from process memory, picking up external changes.
+    pub fn refresh(&mut self, hook: &ZholHook) -> MemOpResult<&T> {
+        let value = self.ptr.read(hook, self.timeout)?;
+
+        Ok(self.shadow.insert(value))
+    }
+}
@@ -0,0 +1,91 @@
+use crate::memory::read::read_bytes;
+use crate::process::SafeHandle;
+use crate::MemOpResult;
+
+use anyhow::anyhow;
+use std::any::type_name;
+use std::time::Duration;
+
+/// A local copy of a region of process memory.
+///
+/// Reads from a snapshot transmute from the copied bytes, so polling many fields of one region
+/// costs a single `ReadProcessMemory` per `refresh()` instead of one per field.
+#[derive(Clone)]
+pub struct RegionSnapshot {
+    pub handle: SafeHandle,
+    pub base: usize,
+    pub timeout: Option<Duration>,
+    bytes: Vec<u8>,
+}
+
+impl RegionSnapshot {
+    /// Copies `size` bytes starting at `base` into a new snapshot.
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// let snapshot = RegionSnapshot::capture(&handle, player_addr, 0x200, None)?;
+    /// let health = snapshot.read::<f32>(0x48)?;
+    /// ```
+    pub fn capture(
+        handle: &SafeHandle,
+        base: usize,
+        size: usize,
+        timeout: Option<Duration>,
+    ) -> MemOpResult<Self> {
+        let mut snapshot = RegionSnapshot {
+            handle: handle.clone(),
+            base,
+            timeout,
+            bytes: vec![0u8; size],
+        };
+        snapshot.refresh()?;
+
+        Ok(snapshot)
+    }
+
+    /// Re-reads the whole region with a single read, replacing the copied bytes.
+    pub fn refresh(&mut self) -> MemOpResult<()> {
+        let size = self.bytes.len();
+        let bytes = read_bytes(&self.handle, self.base, size, self.timeout)?;
+
+        if bytes.len() != size {
+            return Err(anyhow!(
+                "Only read \"{}\" of \"{size}\" bytes for snapshot at \"{:#X}\".",
+                bytes.len(),
+                self.base
+            )
+            .into());
+        }
+
+        self.bytes = bytes;
+
+        Ok(())
+    }
+
+    /// Transmutes a value from the copied bytes at `offset` from the base, without touching the process.
+    pub fn read<T: bytemuck::Pod>(&self, offset: usize) -> MemOpResult<T> {
+        let bytes = self.read_bytes(offset, std::mem::size_of::<T>())?;
+
+        bytemuck::try_pod_read_unaligned::<T>(bytes)
+            .map_err(|e| anyhow!("Failed to convert bytes to \"{}\": {e}", type_name::<T>()).into())
+    }
+
+    /// Returns `size` copied bytes at `offset` from the base.
+    pub fn read_bytes(&self, offset: usize, size: usize) -> MemOpResult<&[u8]> {
+        offset
+            .checked_add(size)
+            .and_then(|end| self.bytes.get(offset..end))
+            .ok_or(
+                anyhow!(
+                    "Read of \"{size:#X}\" bytes at offset \"{offset:#X}\" exceeds snapshot size \"{:#X}\".",
+                    self.bytes.len()
+                )
+                .into(),
+            )
+    }
+
+    /// Returns all copied bytes.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
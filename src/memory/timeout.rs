@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+/// Default timeouts for each kind of memory operation, used whenever a call omits its own.
+///
+/// A `Target` or hook carries a policy, so individual operations can pass `None` and inherit it.
+/// The default policy never times out, so passing `None` keeps waiting without limit unless a policy is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeoutPolicy {
+    pub read: Option<Duration>,
+    pub write: Option<Duration>,
+    /// Pattern scans read whole modules, so they get a longer default. Used by `Target::scan()` and
+    /// the scan for a hook's inject point
+    pub scan: Option<Duration>,
+    /// Acquiring a handle outside of a read/write/scan, e.g. the module queries and reprotection
+    /// done while scanning for a hook's inject point
+    pub acquire: Option<Duration>,
+}
+
+impl Default for TimeoutPolicy {
+    fn default() -> Self {
+        TimeoutPolicy::unbounded()
+    }
+}
+
+impl TimeoutPolicy {
+    /// A policy that never times out, matching passing `None` everywhere. This is the default.
+    pub fn unbounded() -> Self {
+        TimeoutPolicy {
+            read: None,
+            write: None,
+            scan: None,
+            acquire: None,
+        }
+    }
+
+    /// A policy bounding every operation: 1s for reads, writes and acquiring the handle, 10s for scans.
+    pub fn bounded() -> Self {
+        TimeoutPolicy {
+            read: Some(Duration::from_secs(1)),
+            write: Some(Duration::from_secs(1)),
+            scan: Some(Duration::from_secs(10)),
+            acquire: Some(Duration::from_secs(1)),
+        }
+    }
+
+    /// Returns `explicit` if given, otherwise the policy's read timeout.
+    pub fn read(&self, explicit: impl Into<Option<Duration>>) -> Option<Duration> {
+        explicit.into().or(self.read)
+    }
+
+    /// Returns `explicit` if given, otherwise the policy's write timeout.
+    pub fn write(&self, explicit: impl Into<Option<Duration>>) -> Option<Duration> {
+        explicit.into().or(self.write)
+    }
+
+    /// Returns `explicit` if given, otherwise the policy's scan timeout.
+    pub fn scan(&self, explicit: impl Into<Option<Duration>>) -> Option<Duration> {
+        explicit.into().or(self.scan)
+    }
+
+    /// Returns `explicit` if given, otherwise the policy's acquire timeout.
+    pub fn acquire(&self, explicit: impl Into<Option<Duration>>) -> Option<Duration> {
+        explicit.into().or(self.acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeoutPolicy;
+    use std::time::Duration;
+
+    #[test]
+    fn default_policy_is_unbounded() {
+        assert_eq!(TimeoutPolicy::default(), TimeoutPolicy::unbounded());
+        assert_eq!(TimeoutPolicy::default().read(None), None);
+    }
+
+    #[test]
+    fn explicit_timeout_overrides_policy() {
+        let policy = TimeoutPolicy::bounded();
+
+        assert_eq!(policy.read(None), Some(Duration::from_secs(1)));
+        assert_eq!(policy.scan(None), Some(Duration::from_secs(10)));
+        assert_eq!(policy.write(Duration::from_millis(5)), Some(Duration::from_millis(5)));
+    }
+}
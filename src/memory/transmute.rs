@@ -4,15 +4,21 @@ use crate::memory::MemOpContext;
 /// Top-level trait for determining if a type can be used directly from game memory.
 ///
 /// # Info
-/// This trait is a supertrait of Transmutable and bytemuck's Pod.
+/// This trait is a supertrait of Transmutable, whose `Repr` is bound by bytemuck's Pod.
 ///
-/// This is done because Transmutable contains custom to/from byte ops and bytemuck Pod contains the memory-relevant traits.
+/// This is done because Transmutable contains custom to/from byte ops and the Pod `Repr` contains the memory-relevant traits.
 ///
-/// With this design, a type that is merely a pointer can be read in the same way as a regular Transmutable/Pod type.
-pub trait ZholTyped<T>: Transmutable<T> + bytemuck::Pod {}
+/// With this design, a type that is merely a pointer can be read in the same way as a regular Transmutable/Pod type,
+/// even when the type itself (e.g. a `String`) is not Pod.
+pub trait ZholTyped<T>: Transmutable<T> {}
 
 /// Trait for custom "to/from bytes" methods.
 pub trait Transmutable<T> {
+    /// The in-memory representation of the type, used to determine how many bytes are read.
+    ///
+    /// For Pod types this is the type itself. For types living behind a pointer this is the pointer (or struct holding it).
+    type Repr: bytemuck::Pod;
+
     /// For custom implementations where in-memory types need more steps to convert to a hard data type.
     /// 
     /// For instance, this can be used to read memory to get hard data out of a shared pointer.
@@ -28,9 +34,28 @@ pub trait Transmutable<T> {
     fn byte_repr(&self, _hook: &ZholHook, _context: &MemOpContext) -> anyhow::Result<Vec<u8>>;
 }
 
-impl<T: Transmutable<T> + bytemuck::Pod> ZholTyped<T> for T {}
+impl<T: Transmutable<T>> ZholTyped<T> for T {}
 
 /// Marker trait for traits that should use the default impl of Transmutable.
+///
+/// The default impl reads with `bytemuck::try_pod_read_unaligned` and writes with `bytemuck::bytes_of`,
+/// so neither the process address nor the byte buffer has to be aligned for `T`. This makes
+/// `#[repr(C, packed)]` structs (and structs containing packed fields) safe to use directly:
+/// their alignment is 1, so `bytes_of` never sees an unaligned field reference.
+///
+/// # Example
+/// ```rust,norun
+/// #[repr(C, packed)]
+/// #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+/// struct NetHeader {
+///     kind: u8,
+///     id: u32, // at offset 1, unaligned
+/// }
+/// impl AutoImplTransmutable for NetHeader {}
+///
+/// let header = read_value::<NetHeader>(&hook, header_addr, None)?;
+/// write_value(&hook, header_addr, header, None)?;
+/// ```
 pub trait AutoImplTransmutable {}
 impl AutoImplTransmutable for i32 {}
 impl AutoImplTransmutable for i64 {}
@@ -40,6 +65,8 @@ impl AutoImplTransmutable for u32 {}
 impl AutoImplTransmutable for u64 {}
 
 impl<T: bytemuck::Pod + AutoImplTransmutable> Transmutable<T> for T {
+    type Repr = T;
+
     fn transmute_from(
         bytes: &Vec<u8>,
         _hook: &ZholHook,
@@ -58,3 +85,63 @@ impl<T: bytemuck::Pod + AutoImplTransmutable> Transmutable<T> for T {
         Ok(bytemuck::bytes_of::<T>(self).to_vec())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::{Hook, HookData, HookImpl};
+    use crate::memory::{read::read_value, write::write_value, Byte};
+    use std::time::Duration;
+
+    #[repr(C, packed)]
+    #[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+    struct NetHeader {
+        kind: u8,
+        id: u32,
+        scale: f32,
+    }
+    impl AutoImplTransmutable for NetHeader {}
+
+    /// Never hooked, only there to pass a `ZholHook` around.
+    #[derive(Clone)]
+    struct NtdllHook;
+
+    impl HookImpl for NtdllHook {
+        fn pattern(&self) -> &'static [Byte] {
+            &[]
+        }
+
+        fn module_name(&self) -> &'static str {
+            "ntdll.dll"
+        }
+
+        fn build_hook(&self, _hook_data: &HookData) -> anyhow::Result<Vec<u8>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn packed_struct_round_trips_current_process() {
+        let handle = crate::process::open_process(std::process::id()).unwrap();
+        let hook: ZholHook = Hook::new(handle, NtdllHook).unwrap();
+        let context = MemOpContext::new(0, 0, false, None);
+        let header = NetHeader { kind: 7, id: 0x11223344, scale: 1.5 };
+
+        let bytes = header.byte_repr(&hook, &context).unwrap();
+        assert_eq!(bytes.len(), 9);
+        assert_eq!(bytes[..5], [0x07, 0x44, 0x33, 0x22, 0x11]);
+        assert_eq!(bytes[5..], 1.5f32.to_le_bytes());
+
+        let read = NetHeader::transmute_from(&bytes, &hook, &context).unwrap();
+        assert_eq!(read, Some(header));
+
+        // An odd address, so `id` and `scale` are unaligned in the process as well
+        let mut buffer = [0u8; 0x10];
+        let addr = buffer.as_mut_ptr() as usize + 1;
+        let timeout = Some(Duration::from_secs(1));
+
+        write_value(&hook, addr, header, timeout).unwrap();
+        assert_eq!(buffer[1..10], bytes[..]);
+        assert_eq!(read_value::<NetHeader>(&hook, addr, timeout).unwrap(), header);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::error::IntoMemOpResult;
 use crate::memory::MemoryRegion;
-use crate::process::SafeHandle;
+use crate::process::{exited_or, SafeHandle};
 use crate::{with_handle, MemOpError};
 
 use crate::error::{INVALID_ALLOCATION_TYPE, INVALID_PAGE_TYPE, INVALID_PROTECTION_FLAGS};
@@ -9,6 +9,8 @@ use anyhow::anyhow;
 use bytemuck::{Pod, Zeroable};
 use windows::core::PWSTR;
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread::park_timeout;
 use std::time::Duration;
 
@@ -21,6 +23,22 @@ use windows::Win32::System::Memory::{
 
 use windows::Win32::Foundation::HANDLE;
 
+/// Cooperative cancellation token, set to `true` to abort pending operations with `MemOpError::Cancelled`.
+///
+/// Tokens are checked between the steps of an operation, never while a syscall is in flight.
+pub type CancellationToken = Arc<AtomicBool>;
+
+/// Longest single park while waiting with a cancellation token, bounding how late a cancel is noticed.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Returns `MemOpError::Cancelled` if the given token was set.
+pub fn check_cancelled(cancel: Option<&CancellationToken>) -> MemOpResult<()> {
+    match cancel.is_some_and(|c| c.load(Ordering::Acquire)) {
+        true => Err(MemOpError::Cancelled),
+        false => Ok(()),
+    }
+}
+
 pub fn change_memory_protection(
     handle: &SafeHandle,
     addr: usize,
@@ -38,16 +56,197 @@ pub fn change_memory_protection(
                 size,
                 protection,
                 &mut old_protect,
-            ).into_memop_result(Some(anyhow!("VirtualProtectEx in change_memory_protection()")))?
-        };
-        Ok(())
-        // Ok(MemOpResult::Ok(()))
-        //
-    })?;
+            ).into_memop_result(Some(anyhow!("VirtualProtectEx in change_memory_protection()")))
+        }
+    })
+    .map_err(|e| exited_or(handle, e))?;
 
     Ok(old_protect)
 }
 
+/// Changes the protection of a range for as long as the guard lives, restoring the original protection on drop.
+///
+/// Dropping the guard ignores errors restoring the protection; use `restore()` to observe them.
+///
+/// # Example
+/// ```rust,norun
+/// let guard = ProtectionGuard::apply(&handle, addr, 0x10, PAGE_EXECUTE_READWRITE, None)?;
+/// // ... write to the range ...
+/// guard.restore()?;
+/// ```
+pub struct ProtectionGuard {
+    handle: SafeHandle,
+    addr: usize,
+    size: usize,
+    timeout: Option<Duration>,
+    old_protect: PAGE_PROTECTION_FLAGS,
+    restored: bool,
+}
+
+impl ProtectionGuard {
+    /// Changes the protection of a range, see `change_memory_protection()`.
+    pub fn apply(
+        handle: &SafeHandle,
+        addr: usize,
+        size: usize,
+        new_protect: PAGE_PROTECTION_FLAGS,
+        timeout: Option<Duration>,
+    ) -> MemOpResult<Self> {
+        let old_protect = change_memory_protection(handle, addr, size, timeout, new_protect)?;
+
+        Ok(ProtectionGuard {
+            handle: handle.clone(),
+            addr,
+            size,
+            timeout,
+            old_protect,
+            restored: false,
+        })
+    }
+
+    /// Returns the protection the range had before the guard was applied.
+    pub fn old_protection(&self) -> PAGE_PROTECTION_FLAGS {
+        self.old_protect
+    }
+
+    /// Restores the original protection now, returning any error instead of ignoring it on drop.
+    pub fn restore(mut self) -> MemOpResult<()> {
+        self.restored = true;
+        change_memory_protection(&self.handle, self.addr, self.size, self.timeout, self.old_protect)?;
+
+        Ok(())
+    }
+}
+
+impl Drop for ProtectionGuard {
+    fn drop(&mut self) {
+        if !self.restored {
+            _ = change_memory_protection(&self.handle, self.addr, self.size, self.timeout, self.old_protect);
+        }
+    }
+}
+
+/// Sets `PAGE_GUARD` on the page containing `addr`, keeping its other protection flags.
+///
+/// The first access to the page then raises `STATUS_GUARD_PAGE_VIOLATION` in the target and clears the guard,
+/// which a vectored exception handler in the target can use to watch accesses.
+///
+/// # Returns
+/// Returns the protection the page had before
+pub fn set_guard_page(
+    handle: &SafeHandle,
+    addr: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<PAGE_PROTECTION_FLAGS> {
+    let mbi = query_memory(handle, addr, timeout)?;
+
+    change_memory_protection(handle, addr, 1, timeout, mbi.Protect | PAGE_GUARD)
+}
+
+/// Removes `PAGE_GUARD` from the page containing `addr`, keeping its other protection flags.
+///
+/// # Returns
+/// Returns the protection the page had before
+pub fn clear_guard_page(
+    handle: &SafeHandle,
+    addr: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<PAGE_PROTECTION_FLAGS> {
+    let mbi = query_memory(handle, addr, timeout)?;
+
+    change_memory_protection(
+        handle,
+        addr,
+        1,
+        timeout,
+        PAGE_PROTECTION_FLAGS(mbi.Protect.0 & !PAGE_GUARD.0),
+    )
+}
+
+/// Flushes the target's instruction cache for a range, so freshly written code is what gets executed.
+pub fn flush_instruction_cache(
+    handle: &SafeHandle,
+    address: usize,
+    size: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<()> {
+    with_handle!(handle, timeout, |guard| -> (), {
+        unsafe { FlushInstructionCache(*guard, Some(address as *const _), size) }
+            .into_memop_result(Some(anyhow!("FlushInstructionCache in flush_instruction_cache()")))
+    })
+    .map_err(|e| exited_or(handle, e))
+}
+
+/// Queries the region containing `address` with a single `VirtualQueryEx` call.
+pub fn query_memory(
+    handle: &SafeHandle,
+    address: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<MEMORY_BASIC_INFORMATION> {
+    let mut mbi = MEMORY_BASIC_INFORMATION::default();
+
+    #[cfg(feature = "metrics")]
+    crate::metrics::METRICS.record_query();
+
+    with_handle!(handle, timeout, |guard| -> (), {
+        unsafe {
+            if VirtualQueryEx(
+                *guard,
+                Some(address as *const _),
+                &mut mbi,
+                std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+            ) == 0
+            {
+                return MemOpResult::Err(get_last_error())
+            }
+
+            MemOpResult::Ok(())
+        }
+    })
+    .map_err(|e| exited_or(handle, e))?;
+
+    Ok(mbi)
+}
+
+/// Walks the target's address space, returning every committed region that is safe to read.
+pub fn readable_regions(
+    handle: &SafeHandle,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<MEMORY_BASIC_INFORMATION>> {
+    let mut regions = Vec::new();
+    let mut address = 0usize;
+
+    // VirtualQueryEx fails once past the highest user-mode address, ending the walk.
+    while let Ok(mbi) = query_memory(handle, address, timeout) {
+        if mbi.State == MEM_COMMIT && mbi_safe_read(mbi) == 0 {
+            regions.push(mbi);
+        }
+
+        match (mbi.BaseAddress as usize).checked_add(mbi.RegionSize) {
+            Some(next) if next > address => address = next,
+            _ => break,
+        }
+    }
+
+    Ok(regions)
+}
+
+/// Returns whether a queried region covers all of `address..address + size` and already permits the access.
+pub fn region_permits(
+    mbi: &MEMORY_BASIC_INFORMATION,
+    address: usize,
+    size: usize,
+    needs_write: bool,
+) -> bool {
+    let region_end = (mbi.BaseAddress as usize).saturating_add(mbi.RegionSize);
+    let flags = match needs_write {
+        true => mbi_safe_write(*mbi),
+        false => mbi_safe_read(*mbi),
+    };
+
+    flags == 0 && address.saturating_add(size) <= region_end
+}
+
 fn is_readable(protection: PAGE_PROTECTION_FLAGS) -> bool {
     // Define all readable protection constants
     const READABLE_FLAGS: &[u32] = &[
@@ -148,6 +347,7 @@ pub unsafe fn wait_for_safe_mem_unsafe(
     address: usize,
     timeout: Option<Duration>,
     needs_write: bool,
+    cancel: Option<&CancellationToken>,
 ) -> MemOpResult<()> {
     let timeout_dur = match timeout {
         Some(d) => d,
@@ -158,6 +358,11 @@ pub unsafe fn wait_for_safe_mem_unsafe(
     let beginning_park = std::time::Instant::now();
     let mut mbi = MEMORY_BASIC_INFORMATION::default();
     loop {
+        check_cancelled(cancel)?;
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::METRICS.record_query();
+
         unsafe {
             if VirtualQueryEx(
                 handle,
@@ -174,13 +379,19 @@ pub unsafe fn wait_for_safe_mem_unsafe(
             break;
         }
 
-        park_timeout(timeout_remaining);
+        match cancel {
+            Some(_) => park_timeout(timeout_remaining.min(CANCEL_POLL_INTERVAL)),
+            None => park_timeout(timeout_remaining),
+        }
         let elapsed = match timeout {
             Some(_) => beginning_park.elapsed(),
             None => Duration::from_secs(0),
         };
 
         if elapsed >= timeout_dur {
+            #[cfg(feature = "metrics")]
+            crate::metrics::METRICS.record_timeout();
+
             Err(anyhow!("Reached timeout before memory region was readable"))?
         }
 
@@ -192,15 +403,25 @@ pub unsafe fn wait_for_safe_mem_unsafe(
     Ok(())
 }
 
+/// Waits until the memory at `address` is safe to read (or write).
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `address` - Address whose region is checked
+/// * `timeout` - Optional timeout for the handle acquisition and the wait
+/// * `needs_write` - Whether the region also has to be writable
+/// * `cancel` - Optional token, aborting the wait with `MemOpError::Cancelled` once set
 pub fn wait_for_safe_mem(
     handle: &SafeHandle,
     address: usize,
     timeout: Option<Duration>,
     needs_write: bool,
+    cancel: Option<&CancellationToken>,
 ) -> MemOpResult<()> {
     with_handle!(handle, timeout, |guard| -> (), {
-        unsafe { wait_for_safe_mem_unsafe(*guard, address, timeout, needs_write) }
-    })?;
+        unsafe { wait_for_safe_mem_unsafe(*guard, address, timeout, needs_write, cancel) }
+    })
+    .map_err(|e| exited_or(handle, e))?;
 
     Ok(())
 }
@@ -224,23 +445,100 @@ pub fn allocate_memory(handle: &SafeHandle, size: usize) -> MemOpResult<MemoryRe
         }
     })?;
 
-    let clone_handle_ref = handle.clone();
+    Ok(MemoryRegion::from_allocation(handle, addr, size))
+}
 
-    Ok(MemoryRegion {
-        handle: clone_handle_ref,
-        addr,
-        size,
+/// Same as `allocate_memory()`, but the returned region starts at a multiple of `alignment`.
+///
+/// `VirtualAllocEx` already aligns to the allocation granularity (usually 64KB), so larger alignments
+/// over-allocate by `alignment` and hand out the aligned part; the whole allocation is freed on drop.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `size` - Size of the region
+/// * `alignment` - Required alignment of the region's address, a power of two
+pub fn allocate_memory_aligned(
+    handle: &SafeHandle,
+    size: usize,
+    alignment: usize,
+) -> MemOpResult<MemoryRegion> {
+    if !alignment.is_power_of_two() {
+        return Err(anyhow!("Alignment \"{alignment:#X}\" is not a power of two.").into());
+    }
+
+    let region = allocate_memory(handle, size)?;
+    if region.addr % alignment == 0 {
+        return Ok(region);
+    }
+    drop(region);
+
+    let padded = allocate_memory(handle, size + alignment)?;
+    let offset = padded.addr.next_multiple_of(alignment) - padded.addr;
+
+    Ok(padded.subregion(offset, size))
+}
+
+/// Reserves a region of address space in the target without committing any of it.
+///
+/// Pages have to be committed through `MemoryRegion::commit()` (or `write_bytes_committing()`) before use,
+/// so only the pages actually written count towards the target's committed memory.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `size` - Size of the range to reserve, rounded up to the allocation granularity by Windows
+pub fn reserve_memory(handle: &SafeHandle, size: usize) -> MemOpResult<MemoryRegion> {
+    let addr: usize = with_handle!(handle, Some(Duration::from_millis(10)), |guard| -> usize, {
+        unsafe {
+            let addr = VirtualAllocEx(*guard, None, size, MEM_RESERVE, PAGE_NOACCESS);
+
+            if addr.is_null() {
+                return MemOpResult::Err(get_last_error())
+            }
+
+            MemOpResult::Ok(addr as usize)
+        }
+    })?;
+
+    Ok(MemoryRegion::from_allocation(handle, addr, size))
+}
+
+/// Commits the pages spanning `addr..addr + size` of a reserved range as `PAGE_EXECUTE_READWRITE`.
+///
+/// Already committed pages are left as they are, so this is safe to call before every write.
+pub fn commit_memory(
+    handle: &SafeHandle,
+    addr: usize,
+    size: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<()> {
+    with_handle!(handle, timeout, |guard| -> (), {
+        unsafe {
+            let committed = VirtualAllocEx(
+                *guard,
+                Some(addr as *const _),
+                size,
+                MEM_COMMIT,
+                PAGE_EXECUTE_READWRITE,
+            );
+
+            if committed.is_null() {
+                return MemOpResult::Err(get_last_error())
+            }
+
+            MemOpResult::Ok(())
+        }
     })
+    .map_err(|e| exited_or(handle, e))
 }
 
 use windows::Win32::Foundation::GetLastError;
 use windows::Win32::System::Diagnostics::Debug::{
-    FormatMessageW, FORMAT_MESSAGE_ALLOCATE_BUFFER, FORMAT_MESSAGE_FROM_SYSTEM,
-    FORMAT_MESSAGE_IGNORE_INSERTS,
+    FlushInstructionCache, FormatMessageW, FORMAT_MESSAGE_ALLOCATE_BUFFER,
+    FORMAT_MESSAGE_FROM_SYSTEM, FORMAT_MESSAGE_IGNORE_INSERTS,
 };
 use windows_result::HRESULT;
 
-use super::transmute::AutoImplTransmutable;
+use super::transmute::{AutoImplTransmutable, Transmutable};
 
 pub fn get_last_error() -> MemOpError {
     unsafe {
@@ -321,3 +619,304 @@ impl std::fmt::Display for CStr256 {
         write!(f, "{}", self.as_str())
     }
 }
+
+/// An MSVC `std::string` (32-bit layout), read as an owned `String`.
+///
+/// Strings shorter than 16 bytes are stored inline; longer ones are followed through the heap pointer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StdString(pub String);
+
+impl StdString {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Transmutable<StdString> for StdString {
+    /// Buffer/pointer union (0x10), size (0x4) and capacity (0x4).
+    type Repr = [u8; 0x18];
+
+    fn transmute_from(
+        bytes: &Vec<u8>,
+        hook: &crate::hooks::ZholHook,
+        context: &crate::memory::MemOpContext,
+    ) -> anyhow::Result<Option<StdString>> {
+        if bytes.len() < 0x18 {
+            return Ok(None);
+        }
+
+        let len = u32::from_le_bytes([bytes[0x10], bytes[0x11], bytes[0x12], bytes[0x13]]) as usize;
+        let capacity =
+            u32::from_le_bytes([bytes[0x14], bytes[0x15], bytes[0x16], bytes[0x17]]) as usize;
+
+        let raw = if capacity >= 0x10 {
+            let ptr = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+            crate::memory::read::read_bytes(&hook.handle(), ptr, len, context.timeout)
+                .map_err(|e| anyhow!("Reading heap buffer of StdString at \"{ptr:#X}\": {e}"))?
+        } else {
+            bytes[..len.min(0x0F)].to_vec()
+        };
+
+        Ok(Some(StdString(String::from_utf8_lossy(&raw).into_owned())))
+    }
+
+    fn byte_repr(
+        &self,
+        _hook: &crate::hooks::ZholHook,
+        _context: &crate::memory::MemOpContext,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(anyhow!(
+            "StdString cannot be written in place, as its buffer is owned by the target process."
+        ))
+    }
+}
+
+impl std::fmt::Display for StdString {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An IEEE 754 half-precision (16-bit) float, as stored by some GPU buffers and game data.
+///
+/// Read it like any other value and convert with `to_f32()`/`from_f32()` (or `From`).
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+pub struct Half(pub u16);
+impl AutoImplTransmutable for Half {}
+
+impl Half {
+    /// Converts to an `f32`, which can represent every half value exactly.
+    pub fn to_f32(self) -> f32 {
+        let sign = ((self.0 & 0x8000) as u32) << 16;
+        let exp = ((self.0 >> 10) & 0x1F) as u32;
+        let mant = (self.0 & 0x3FF) as u32;
+
+        match exp {
+            0 => {
+                // Zero or subnormal, mant * 2^-24
+                let value = mant as f32 * f32::from_bits(0x3380_0000);
+                f32::from_bits(value.to_bits() | sign)
+            }
+            0x1F => f32::from_bits(sign | 0x7F80_0000 | (mant << 13)),
+            _ => f32::from_bits(sign | ((exp + 112) << 23) | (mant << 13)),
+        }
+    }
+
+    /// Converts from an `f32`, rounding to nearest-even. Values out of range become infinity.
+    pub fn from_f32(value: f32) -> Self {
+        let bits = value.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let exp = ((bits >> 23) & 0xFF) as i32;
+        let mant = bits & 0x7F_FFFF;
+
+        if exp == 0xFF {
+            return match mant {
+                0 => Half(sign | 0x7C00),
+                _ => Half(sign | 0x7E00 | (mant >> 13) as u16),
+            };
+        }
+
+        let half_exp = exp - 127 + 15;
+        if half_exp >= 0x1F {
+            return Half(sign | 0x7C00);
+        }
+
+        if half_exp <= 0 {
+            if half_exp < -10 {
+                return Half(sign);
+            }
+
+            // Subnormal, shift the implicit bit in and round away the rest
+            let full = mant | 0x80_0000;
+            let shift = (14 - half_exp) as u32;
+            let mut half_mant = full >> shift;
+            let rem = full & ((1 << shift) - 1);
+            let halfway = 1 << (shift - 1);
+            if rem > halfway || (rem == halfway && half_mant & 1 == 1) {
+                half_mant += 1;
+            }
+
+            return Half(sign | half_mant as u16);
+        }
+
+        // A rounding carry out of the mantissa correctly bumps the exponent (up to infinity)
+        let mut half = ((half_exp as u32) << 10) | (mant >> 13);
+        let rem = mant & 0x1FFF;
+        if rem > 0x1000 || (rem == 0x1000 && half & 1 == 1) {
+            half += 1;
+        }
+
+        Half(sign | half as u16)
+    }
+}
+
+impl From<Half> for f32 {
+    fn from(value: Half) -> Self {
+        value.to_f32()
+    }
+}
+
+impl From<f32> for Half {
+    fn from(value: f32) -> Self {
+        Half::from_f32(value)
+    }
+}
+
+impl std::fmt::Display for Half {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_f32())
+    }
+}
+
+/// A high-resolution tick count (`LARGE_INTEGER`/`u64`, e.g. from `QueryPerformanceCounter`), read with the
+/// frequency it ticks at.
+///
+/// `FREQUENCY` is in ticks per second and defaults to 10MHz, what `QueryPerformanceFrequency` reports on
+/// current Windows versions. Games with their own clocks can pass theirs, e.g. `GameTime<1000>` for milliseconds.
+///
+/// # Example
+/// ```rust,norun
+/// let uptime = read_value::<GameTime>(&hook, game_clock_addr, None)?;
+/// println!("Running for {:.1}s", uptime.as_seconds());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GameTime<const FREQUENCY: u64 = 10_000_000> {
+    pub ticks: u64,
+}
+
+impl<const FREQUENCY: u64> GameTime<FREQUENCY> {
+    pub fn new(ticks: u64) -> Self {
+        GameTime { ticks }
+    }
+
+    /// Ticks per second.
+    pub fn frequency(&self) -> u64 {
+        FREQUENCY
+    }
+
+    /// Converts the tick count to seconds.
+    pub fn as_seconds(&self) -> f64 {
+        self.ticks as f64 / FREQUENCY as f64
+    }
+}
+
+impl<const FREQUENCY: u64> Transmutable<GameTime<FREQUENCY>> for GameTime<FREQUENCY> {
+    type Repr = u64;
+
+    fn transmute_from(
+        bytes: &Vec<u8>,
+        _hook: &crate::hooks::ZholHook,
+        _context: &crate::memory::MemOpContext,
+    ) -> anyhow::Result<Option<GameTime<FREQUENCY>>> {
+        let ticks = bytemuck::try_pod_read_unaligned::<u64>(bytes)
+            .map_err(|e| anyhow!("Failed to convert bytes to GameTime: {e}"))?;
+
+        Ok(Some(GameTime { ticks }))
+    }
+
+    fn byte_repr(
+        &self,
+        _hook: &crate::hooks::ZholHook,
+        _context: &crate::memory::MemOpContext,
+    ) -> anyhow::Result<Vec<u8>> {
+        Ok(self.ticks.to_le_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_cancelled_reports_set_token() {
+        let token: CancellationToken = Arc::new(AtomicBool::new(false));
+        assert!(check_cancelled(None).is_ok());
+        assert!(check_cancelled(Some(&token)).is_ok());
+
+        token.store(true, Ordering::Release);
+        assert!(matches!(check_cancelled(Some(&token)), Err(MemOpError::Cancelled)));
+    }
+
+    #[test]
+    fn half_converts_known_values() {
+        assert_eq!(Half(0x3C00).to_f32(), 1.0);
+        assert_eq!(Half(0xC000).to_f32(), -2.0);
+        assert_eq!(Half(0x7BFF).to_f32(), 65504.0);
+        assert_eq!(Half(0x3555).to_f32(), 1365.0 / 4096.0);
+
+        assert_eq!(Half::from_f32(1.0), Half(0x3C00));
+        assert_eq!(Half::from_f32(-2.0), Half(0xC000));
+        assert_eq!(Half::from_f32(-0.0), Half(0x8000));
+        assert_eq!(Half::from(65504.0), Half(0x7BFF));
+    }
+
+    #[test]
+    fn half_round_trips_every_non_nan_value() {
+        for bits in 0..=u16::MAX {
+            let half = Half(bits);
+            if half.to_f32().is_nan() {
+                continue;
+            }
+
+            assert_eq!(Half::from_f32(half.to_f32()), half, "{bits:#06X}");
+        }
+    }
+
+    #[test]
+    fn half_rounds_to_nearest_even() {
+        let ulp = 2f32.powi(-10);
+
+        // Ties go to the even neighbour: 0x3C00 rather than 0x3C01, 0x3C02 rather than 0x3C01
+        assert_eq!(Half::from_f32(1.0 + ulp / 2.0), Half(0x3C00));
+        assert_eq!(Half::from_f32(1.0 + ulp * 1.5), Half(0x3C02));
+        assert_eq!(Half::from_f32(1.0 + ulp * 0.75), Half(0x3C01));
+
+        // Rounding past the largest finite value carries into infinity
+        assert_eq!(Half::from_f32(65519.0), Half(0x7BFF));
+        assert_eq!(Half::from_f32(65520.0), Half(0x7C00));
+    }
+
+    #[test]
+    fn half_converts_subnormals() {
+        let min = 2f32.powi(-24);
+
+        assert_eq!(Half(0x0001).to_f32(), min);
+        assert_eq!(Half(0x03FF).to_f32(), min * 1023.0);
+        assert_eq!(Half(0x8001).to_f32(), -min);
+
+        assert_eq!(Half::from_f32(min), Half(0x0001));
+        assert_eq!(Half::from_f32(min * 1023.0), Half(0x03FF));
+        assert_eq!(Half::from_f32(min / 2.0), Half(0x0000));
+        assert_eq!(Half::from_f32(min * 1.5), Half(0x0002));
+        assert_eq!(Half::from_f32(min / 4.0), Half(0x0000));
+    }
+
+    #[test]
+    fn half_converts_infinity_and_nan() {
+        assert_eq!(Half(0x7C00).to_f32(), f32::INFINITY);
+        assert_eq!(Half(0xFC00).to_f32(), f32::NEG_INFINITY);
+        assert!(Half(0x7E00).to_f32().is_nan());
+
+        assert_eq!(Half::from_f32(f32::INFINITY), Half(0x7C00));
+        assert_eq!(Half::from_f32(f32::NEG_INFINITY), Half(0xFC00));
+        assert_eq!(Half::from_f32(1e10), Half(0x7C00));
+
+        let nan = Half::from_f32(f32::NAN);
+        assert_eq!(nan.0 & 0x7C00, 0x7C00);
+        assert_ne!(nan.0 & 0x3FF, 0);
+    }
+
+    #[test]
+    fn game_time_as_seconds() {
+        let time: GameTime = GameTime::new(25_000_000);
+        assert_eq!(time.frequency(), 10_000_000);
+        assert_eq!(time.as_seconds(), 2.5);
+
+        let time = GameTime::<1000>::new(1500);
+        assert_eq!(time.frequency(), 1000);
+        assert_eq!(time.as_seconds(), 1.5);
+
+        assert_eq!(GameTime::<60>::new(0).as_seconds(), 0.0);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::error::IntoMemOpResult;
 // use crate::hooks::hook::HookOps;
 use crate::hooks::ZholHook;
-use crate::memory::utils::{change_memory_protection, wait_for_safe_mem};
-use crate::process::SafeHandle;
+use crate::memory::utils::{
+    check_cancelled, commit_memory, flush_instruction_cache, query_memory, region_permits,
+    wait_for_safe_mem, CancellationToken, ProtectionGuard,
+};
+use crate::process::{exited_or, SafeHandle};
 use crate::{with_handle, MemOpResult};
 use anyhow::anyhow;
 use std::time::Duration;
@@ -12,23 +15,61 @@ use windows::Win32::System::Memory::PAGE_EXECUTE_READWRITE;
 
 // use crate::memory::transmute::Transmutable;
 
+use super::read::{bitfield_span, read_bytes};
 use super::transmute::ZholTyped;
 use super::MemOpContext;
 
 /// Writes a given byte slice to an address in process memory.
+///
+/// Memory protection is only changed (and restored afterwards) if the range isn't already writable.
 pub fn write_bytes(
     handle: &SafeHandle,
     addr: usize,
     bytes: &[u8],
     timeout: Option<Duration>,
+) -> MemOpResult<()> {
+    write_bytes_cancellable(handle, addr, bytes, timeout, None)
+}
+
+/// Same as `write_bytes()`, but aborts with `MemOpError::Cancelled` if `cancel` is set before any of its steps.
+///
+/// Once memory protection was changed, the original protection is still restored before returning `Cancelled`.
+pub fn write_bytes_cancellable(
+    handle: &SafeHandle,
+    addr: usize,
+    bytes: &[u8],
+    timeout: Option<Duration>,
+    cancel: Option<&CancellationToken>,
 ) -> MemOpResult<()> {
     let mut bytes_written: usize = 0;
     let size = bytes.len();
 
-    let old_protect =
-        change_memory_protection(handle, addr, size, timeout, PAGE_EXECUTE_READWRITE)?;
+    check_cancelled(cancel)?;
+
+    // Skip the protect/restore pair when the whole range is already writable, e.g. scratch memory.
+    let already_writable = query_memory(handle, addr, timeout)
+        .is_ok_and(|mbi| region_permits(&mbi, addr, size, true));
+
+    let protection = match already_writable {
+        true => None,
+        false => Some(ProtectionGuard::apply(
+            handle,
+            addr,
+            size,
+            PAGE_EXECUTE_READWRITE,
+            timeout,
+        )?),
+    };
+
+    if let Err(e) = wait_for_safe_mem(handle, addr, timeout, true, cancel)
+        .and_then(|_| check_cancelled(cancel))
+    {
+        if let Some(protection) = protection {
+            protection.restore()?;
+        }
+        return Err(e);
+    }
 
-    wait_for_safe_mem(handle, addr, timeout, true)?;
     with_handle!(&handle, timeout, |guard| -> (), {
         unsafe {
             // Write the bytes
@@ -38,25 +79,85 @@ pub fn write_bytes(
                 bytes.as_ptr() as *const _,
                 bytes.len(),
                 Some(&mut bytes_written),
-            ).into_memop_result(Some(anyhow!("WriteProcessMemory in write_bytes()")))?
-        };
-        Ok(())
-    })?;
+            ).into_memop_result(Some(anyhow!("WriteProcessMemory in write_bytes()")))
+        }
+    })
+    .map_err(|e| exited_or(handle, e))?;
+
+    #[cfg(feature = "metrics")]
+    crate::metrics::METRICS.record_write(bytes_written);
 
-    change_memory_protection(handle, addr, size, timeout, old_protect)?;
+    if let Some(protection) = protection {
+        protection.restore()?;
+    }
 
     // Verify all bytes were written
     if bytes_written != bytes.len() {
         return Err(anyhow!("An error prevented all bytes from being written.").into());
     }
 
-    wait_for_safe_mem(handle, addr, timeout, true)?;
+    wait_for_safe_mem(handle, addr, timeout, true, None)?;
 
     std::thread::sleep(Duration::from_nanos(1));
 
     Ok(())
 }
 
+/// Same as `write_bytes()`, but first commits the pages being written to.
+///
+/// Meant for regions from `reserve_memory()`, where pages are only committed once they're written.
+pub fn write_bytes_committing(
+    handle: &SafeHandle,
+    addr: usize,
+    bytes: &[u8],
+    timeout: Option<Duration>,
+) -> MemOpResult<()> {
+    commit_memory(handle, addr, bytes.len(), timeout)?;
+    write_bytes(handle, addr, bytes, timeout)
+}
+
+/// Patches code: writes the bytes, flushes the instruction cache for them and reads them back to verify.
+///
+/// Catches the common ways a code patch silently fails, like a stale instruction cache or a protection
+/// the target reverted. The patch is left in place when verification fails.
+///
+/// # Returns
+/// Returns the bytes that were overwritten, for restoring them later
+///
+/// # Example
+/// ```rust,norun
+/// // nop out a 2-byte conditional jump
+/// let original = patch_code(&handle, jz_addr, &[0x90, 0x90], None)?;
+/// // ...
+/// patch_code(&handle, jz_addr, &original, None)?;
+/// ```
+pub fn patch_code(
+    handle: &SafeHandle,
+    addr: usize,
+    bytes: &[u8],
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<u8>> {
+    let original = read_bytes(handle, addr, bytes.len(), timeout)?;
+
+    write_bytes(handle, addr, bytes, timeout)?;
+    flush_instruction_cache(handle, addr, bytes.len(), timeout)?;
+
+    let written = read_bytes(handle, addr, bytes.len(), timeout)?;
+    if written.len() != bytes.len() {
+        return Err(anyhow!("Only \"{:#X}\" bytes of the patch at \"{addr:#X}\" could be read back.", written.len()).into());
+    }
+    if let Some(offset) = changed_spans(bytes, &written).first().map(|span| span.start) {
+        return Err(anyhow!(
+            "Patch at \"{addr:#X}\" didn't stick, byte \"{offset:#X}\" reads \"{:02X}\" instead of \"{:02X}\".",
+            written[offset],
+            bytes[offset]
+        )
+        .into());
+    }
+
+    Ok(original)
+}
+
 /// Transmutes a value to a byte slice and writes it to a given address in process memory.
 pub fn write_value<T: ZholTyped<T>>(
     hook: &ZholHook,
@@ -75,3 +176,280 @@ pub fn write_value<T: ZholTyped<T>>(
 
     Ok(())
 }
+
+/// Writes a contiguous array of values with a single `write_bytes()`, the counterpart of `read_array()`.
+///
+/// Elements are serialized with their `byte_repr()` and must each produce exactly `size_of::<T::Repr>()` bytes,
+/// so they land where `read_array()` expects them.
+///
+/// # Example
+/// ```rust,norun
+/// write_array::<i32>(&hook, table_addr, &[0; 64], None)?;
+/// ```
+pub fn write_array<T: ZholTyped<T>>(
+    hook: &ZholHook,
+    address: usize,
+    values: &[T],
+    timeout: Option<Duration>,
+) -> MemOpResult<()> {
+    if values.is_empty() {
+        return Ok(());
+    }
+
+    let stride = std::mem::size_of::<T::Repr>();
+    let mut buffer = Vec::with_capacity(stride * values.len());
+
+    for (i, value) in values.iter().enumerate() {
+        let element_addr = address + i * stride;
+        let context = MemOpContext::new(element_addr, 0x0, false, timeout);
+        let bytes = value.byte_repr(hook, &context)?;
+
+        if bytes.len() != stride {
+            return Err(anyhow!(
+                "Element \"{i}\" of \"{}\" serialized to \"{}\" bytes, expected \"{stride}\".",
+                std::any::type_name::<T>(),
+                bytes.len()
+            )
+            .into());
+        }
+
+        buffer.extend_from_slice(&bytes);
+    }
+
+    write_bytes(&hook.handle(), address, &buffer, timeout)
+}
+
+/// Writes a `Pod` value to memory, only issuing writes for the bytes that changed since its last write.
+///
+/// The first write (and any write to a different address) writes the whole value.
+/// Remote changes made between writes are not noticed, as only the last-written bytes are compared.
+///
+/// # Example
+/// ```rust,norun
+/// let mut writer = DiffWriter::<PlayerState>::new();
+/// writer.write_diff(&hook, state_addr, &state, None)?;
+/// state.health = 100;
+/// writer.write_diff(&hook, state_addr, &state, None)?; // Only writes `health`
+/// ```
+pub struct DiffWriter<T: bytemuck::Pod> {
+    last: Option<(usize, Vec<u8>)>,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> Default for DiffWriter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: bytemuck::Pod> DiffWriter<T> {
+    pub fn new() -> Self {
+        DiffWriter {
+            last: None,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Forgets the last-written bytes, so the next write covers the whole value.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// Writes the spans of `new` that differ from the last write, one `write_bytes()` per span.
+    ///
+    /// # Returns
+    /// Returns the byte ranges (relative to `address`) that were written
+    pub fn write_diff(
+        &mut self,
+        hook: &ZholHook,
+        address: usize,
+        new: &T,
+        timeout: Option<Duration>,
+    ) -> MemOpResult<Vec<std::ops::Range<usize>>> {
+        let new_bytes = bytemuck::bytes_of(new);
+
+        let last = match &mut self.last {
+            Some((last_addr, last)) if *last_addr == address => last,
+            _ => {
+                write_bytes(&hook.handle(), address, new_bytes, timeout)?;
+                self.last = Some((address, new_bytes.to_vec()));
+
+                let whole = 0..new_bytes.len();
+                return Ok(Vec::from([whole]));
+            }
+        };
+
+        let spans = changed_spans(last, new_bytes);
+        for span in &spans {
+            write_bytes(&hook.handle(), address + span.start, &new_bytes[span.clone()], timeout)?;
+            // Only spans that made it to memory are remembered, so a failed span is retried next time.
+            last[span.clone()].copy_from_slice(&new_bytes[span.clone()]);
+        }
+
+        Ok(spans)
+    }
+}
+
+/// Returns the ranges where two equally long byte slices differ, with adjacent differing bytes coalesced.
+pub fn changed_spans(old: &[u8], new: &[u8]) -> Vec<std::ops::Range<usize>> {
+    let mut spans: Vec<std::ops::Range<usize>> = Vec::new();
+
+    for i in (0..old.len().min(new.len())).filter(|&i| old[i] != new[i]) {
+        match spans.last_mut() {
+            Some(span) if span.end == i => span.end = i + 1,
+            _ => spans.push(i..i + 1),
+        }
+    }
+
+    spans
+}
+
+/// What `increment_value()` does when the addition overflows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wrap around, like `wrapping_add`
+    Wrapping,
+    /// Clamp to the type's bounds, like `saturating_add`
+    Saturating,
+    /// Error without writing
+    Checked,
+}
+
+/// Integers `increment_value()` can add to, under each `OverflowPolicy`.
+pub trait IncrementInt: Copy + std::fmt::Display {
+    fn add_with(self, delta: Self, policy: OverflowPolicy) -> Option<Self>;
+}
+
+macro_rules! impl_increment_int {
+    ($($t:ty),*) => {
+        $(impl IncrementInt for $t {
+            fn add_with(self, delta: Self, policy: OverflowPolicy) -> Option<Self> {
+                match policy {
+                    OverflowPolicy::Wrapping => Some(self.wrapping_add(delta)),
+                    OverflowPolicy::Saturating => Some(self.saturating_add(delta)),
+                    OverflowPolicy::Checked => self.checked_add(delta),
+                }
+            }
+        })*
+    };
+}
+
+impl_increment_int!(i32, i64, u32, u64);
+
+/// Adds `delta` to the integer a context points at, writing the sum back.
+///
+/// The address is resolved once and `timeout` is used for both the read and the write. This is not atomic,
+/// so a write by the target between the two is lost.
+///
+/// # Returns
+/// Returns the new value
+///
+/// # Example
+/// ```rust,norun
+/// let score = increment_value::<i32>(&hook, &context, 100, OverflowPolicy::Checked, None)?;
+/// ```
+pub fn increment_value<T: ZholTyped<T> + IncrementInt>(
+    hook: &ZholHook,
+    context: &MemOpContext,
+    delta: T,
+    policy: OverflowPolicy,
+    timeout: Option<Duration>,
+) -> MemOpResult<T> {
+    let addr = crate::memory::resolve_address(hook, context)?;
+
+    let current = crate::memory::read::read_value::<T>(hook, addr, timeout)?;
+    let updated = incremented(current, delta, policy, addr)?;
+
+    write_value(hook, addr, updated, timeout)?;
+
+    Ok(updated)
+}
+
+/// Adds `delta` to the value read from `addr` under `policy`, erroring if a checked addition overflows.
+fn incremented<T: IncrementInt>(
+    current: T,
+    delta: T,
+    policy: OverflowPolicy,
+    addr: usize,
+) -> MemOpResult<T> {
+    let updated = current.add_with(delta, policy).ok_or_else(|| {
+        anyhow!(
+            "Adding \"{delta}\" to \"{current}\" at \"{addr:#X}\" overflows \"{}\".",
+            std::any::type_name::<T>()
+        )
+    })?;
+
+    Ok(updated)
+}
+
+/// Writes `bit_width` bits starting at `bit_offset` of the (little-endian) integer a context points at.
+///
+/// This is a read-modify-write of only the bytes spanned by the bitfield, so neighboring bits are preserved.
+/// `timeout` falls back to the context's, then the hook's policy.
+pub fn write_bitfield(
+    hook: &ZholHook,
+    context: &MemOpContext,
+    bit_offset: u32,
+    bit_width: u32,
+    value: u64,
+    timeout: Option<Duration>,
+) -> MemOpResult<()> {
+    let (size, mask) = bitfield_span(bit_offset, bit_width)?;
+    if value & !mask != 0 {
+        return Err(anyhow!("Value \"{value:#X}\" does not fit in a bitfield of width \"{bit_width}\".").into());
+    }
+
+    let addr = crate::memory::resolve_address(hook, context)?;
+    let timeout = timeout.or(context.timeout);
+
+    let mut raw = [0u8; 8];
+    let bytes = read_bytes(&hook.handle(), addr, size, hook.timeouts().read(timeout))?;
+    raw[..bytes.len()].copy_from_slice(&bytes);
+
+    let current = u64::from_le_bytes(raw);
+    let updated = (current & !(mask << bit_offset)) | (value << bit_offset);
+
+    write_bytes(&hook.handle(), addr, &updated.to_le_bytes()[..size], hook.timeouts().write(timeout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_with_wraps_i32() {
+        assert_eq!(i32::MAX.add_with(1, OverflowPolicy::Wrapping), Some(i32::MIN));
+        assert_eq!(5i32.add_with(-7, OverflowPolicy::Wrapping), Some(-2));
+    }
+
+    #[test]
+    fn incremented_checked_i32_overflow_is_an_error() {
+        assert!(incremented(i32::MAX, 1, OverflowPolicy::Checked, 0x1000).is_err());
+        assert!(incremented(i32::MIN, -1, OverflowPolicy::Checked, 0x1000).is_err());
+        assert_eq!(incremented(40i32, 2, OverflowPolicy::Checked, 0x1000).unwrap(), 42);
+    }
+
+    #[test]
+    fn add_with_saturates_u32() {
+        assert_eq!(u32::MAX.add_with(1, OverflowPolicy::Saturating), Some(u32::MAX));
+    }
+
+    #[test]
+    fn changed_spans_coalesces_adjacent_bytes() {
+        let old = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66];
+        let new = [0xFF, 0x11, 0xEE, 0xDD, 0x44, 0x55, 0xAA];
+
+        assert_eq!(changed_spans(&old, &new), vec![0..1, 2..4, 6..7]);
+    }
+
+    #[test]
+    fn changed_spans_of_equal_slices_is_empty() {
+        assert!(changed_spans(&[0x90; 0x10], &[0x90; 0x10]).is_empty());
+        assert!(changed_spans(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn changed_spans_covers_whole_slices() {
+        assert_eq!(changed_spans(&[0x00; 0x4], &[0xFF; 0x4]), vec![0..4]);
+    }
+}
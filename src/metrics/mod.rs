@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Process-wide counters of the memory operations performed through the crate.
+///
+/// Only compiled with the `metrics` feature, so the hot paths carry no counting otherwise.
+///
+/// # Example
+/// ```rust,norun
+/// let before = zhol::metrics::METRICS.snapshot();
+/// // ... reads ...
+/// let after = zhol::metrics::METRICS.snapshot();
+/// println!("{} reads", after.reads - before.reads);
+/// ```
+pub struct Metrics {
+    reads: AtomicU64,
+    writes: AtomicU64,
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    queries: AtomicU64,
+    timeouts: AtomicU64,
+}
+
+/// A point-in-time copy of the counters in `Metrics`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// `ReadProcessMemory` calls
+    pub reads: u64,
+    /// `WriteProcessMemory` calls
+    pub writes: u64,
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    /// `VirtualQueryEx` calls
+    pub queries: u64,
+    /// Handle acquisitions and memory waits that ran out of time
+    pub timeouts: u64,
+}
+
+/// The counters every operation in the crate records to.
+pub static METRICS: Metrics = Metrics::new();
+
+impl Metrics {
+    const fn new() -> Self {
+        Metrics {
+            reads: AtomicU64::new(0),
+            writes: AtomicU64::new(0),
+            bytes_read: AtomicU64::new(0),
+            bytes_written: AtomicU64::new(0),
+            queries: AtomicU64::new(0),
+            timeouts: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the current counts.
+    ///
+    /// Counters are read one at a time, so operations running concurrently may be partially included.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            reads: self.reads.load(Ordering::Relaxed),
+            writes: self.writes.load(Ordering::Relaxed),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            queries: self.queries.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets every counter back to zero.
+    pub fn reset(&self) {
+        for counter in [
+            &self.reads,
+            &self.writes,
+            &self.bytes_read,
+            &self.bytes_written,
+            &self.queries,
+            &self.timeouts,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn record_read(&self, bytes: usize) {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+        self.bytes_read.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_write(&self, bytes: usize) {
+        self.writes.fetch_add(1, Ordering::Relaxed);
+        self.bytes_written.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_query(&self) {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_timeout(&self) {
+        self.timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -2,8 +2,20 @@
 // pub mod input;
 pub mod module;
 pub mod pattern;
+pub mod pe;
+pub mod remote;
+#[cfg(feature = "pdb")]
+pub mod symbols;
+pub mod target;
+pub mod thread;
 // pub mod utils;
 
+pub use remote::{call_remote, execute_shellcode, CallingConvention};
+#[cfg(feature = "pdb")]
+pub use symbols::resolve_symbol;
+pub use target::{attach, Target};
+pub use thread::{open_thread, read_thread_stack, SafeThreadHandle};
+
 /// A macro for safely acquiring and using a handle with timeout support.
 /// 
 /// This macro provides a convenient way to acquire a handle with an optional timeout,
@@ -119,7 +131,74 @@ impl std::ops::Deref for RawHandle { // BAD, rework sometime :) -S
 }
 
 use parking_lot::{Mutex, MutexGuard};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use windows::Win32::Foundation::WAIT_TIMEOUT;
+use windows::Win32::System::Threading::{
+    GetProcessId, OpenProcess, WaitForSingleObject, PROCESS_ALL_ACCESS,
+};
+
+use crate::{MemOpError, MemOpResult};
+
+/// Opens a process by its PID with full access.
+///
+/// # Arguments
+///
+/// * `pid` - The PID of the process to open
+///
+/// # Returns
+///
+/// Returns a `SafeHandle` to the process, which reports the PID through `SafeHandle::pid()`.
+///
+/// # Examples
+///
+/// ```rust,norun
+/// use zhol::process::open_process;
+///
+/// let handle = open_process(pid)?;
+/// assert_eq!(handle.pid(), Some(pid));
+/// ```
+pub fn open_process(pid: u32) -> MemOpResult<SafeHandle> {
+    let handle = unsafe { OpenProcess(PROCESS_ALL_ACCESS, false, pid)? };
+
+    Ok(SafeHandle::with_pid(handle, Some(pid)))
+}
+
+/// Replaces an error with `MemOpError::ProcessExited` if it was caused by the process behind `handle` exiting.
+///
+/// Only WinAPI failures an exited process produces (invalid handle, access denied, partial copy) are checked,
+/// and only when the process is confirmed gone, so permission problems still surface as-is.
+/// Unlike `is_alive()`, this never waits for the handle: if another thread holds it, `err` is returned unchanged.
+pub fn exited_or(handle: &SafeHandle, err: MemOpError) -> MemOpError {
+    if !err.is_exit_symptom() {
+        return err;
+    }
+
+    match handle.try_acquire() {
+        Some(guard) if unsafe { WaitForSingleObject(*guard, 0) } != WAIT_TIMEOUT => {
+            MemOpError::ProcessExited
+        }
+        _ => err,
+    }
+}
+
+/// Checks whether the process behind a handle is still running.
+///
+/// A process handle is signaled once the process exits, so a zero-length wait timing out means it is alive.
+/// This blocks until the handle can be acquired.
+///
+/// # Examples
+///
+/// ```rust,norun
+/// while zhol::process::is_alive(&handle) {
+///     poll_values(&handle)?;
+/// }
+/// ```
+pub fn is_alive(handle: &SafeHandle) -> bool {
+    match handle.acquire_with_timeout(None) {
+        Some(guard) => unsafe { WaitForSingleObject(*guard, 0) == WAIT_TIMEOUT },
+        None => false,
+    }
+}
 
 /// A thread-safe wrapper for Windows handles with timeout-based locking.
 /// 
@@ -133,6 +212,10 @@ use std::time::Duration;
 pub struct SafeHandle {
     /// The mutex-protected handle wrapped in an Arc for shared ownership
     inner: Arc<Mutex<Arc<RawHandle>>>,
+    /// The PID of the process the handle was opened for, if known
+    pid: Option<u32>,
+    /// When the current holder acquired the handle, `None` while it isn't held
+    acquired_at: Arc<Mutex<Option<Instant>>>,
 }
 
 impl Clone for SafeHandle {
@@ -143,6 +226,8 @@ impl Clone for SafeHandle {
     fn clone(&self) -> Self {
         SafeHandle {
             inner: Arc::clone(&self.inner),
+            pid: self.pid,
+            acquired_at: Arc::clone(&self.acquired_at),
         }
     }
 }
@@ -162,6 +247,29 @@ unsafe impl Sync for SafeHandle {}
 pub struct SafeHandleGuard<'a> {
     /// The mutex guard that maintains exclusive access to the handle
     _guard: MutexGuard<'a, Arc<RawHandle>>,
+    /// When the handle was acquired
+    acquired_at: Instant,
+    /// The handle's record of the current holder's acquisition time, cleared on drop
+    holder: &'a Mutex<Option<Instant>>,
+}
+
+impl SafeHandleGuard<'_> {
+    /// Returns when the handle was acquired.
+    pub fn acquired_at(&self) -> Instant {
+        self.acquired_at
+    }
+
+    /// Returns how long the handle has been held through this guard.
+    pub fn held_for(&self) -> Duration {
+        self.acquired_at.elapsed()
+    }
+}
+
+impl Drop for SafeHandleGuard<'_> {
+    fn drop(&mut self) {
+        // Cleared before the handle mutex itself is released, so the next holder's timestamp isn't overwritten.
+        *self.holder.lock() = None;
+    }
 }
 
 impl SafeHandle {
@@ -179,12 +287,29 @@ impl SafeHandle {
     /// let safe_handle = SafeHandle::new(some_windows_handle);
     /// ```
     pub fn new(handle: HANDLE) -> Self {
+        // GetProcessId returns 0 for handles that aren't process handles.
+        let pid = match unsafe { GetProcessId(handle) } {
+            0 => None,
+            pid => Some(pid),
+        };
+
+        Self::with_pid(handle, pid)
+    }
+
+    fn with_pid(handle: HANDLE, pid: Option<u32>) -> Self {
         let raw_handle = Arc::new(RawHandle::new(handle));
         SafeHandle {
             inner: Arc::new(Mutex::new(raw_handle)),
+            pid,
+            acquired_at: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Returns the PID of the process this handle was opened for, if known.
+    pub fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
     /// Attempts to acquire exclusive access to the handle with an optional timeout.
     /// 
     /// # Arguments
@@ -212,10 +337,76 @@ impl SafeHandle {
     /// ```
     pub fn acquire_with_timeout(&self, timeout: Option<Duration>) -> Option<SafeHandleGuard<'_>> {
         match timeout {
-            Some(duration) => self.inner.try_lock_for(duration),
+            Some(duration) => {
+                let guard = self.inner.try_lock_for(duration);
+
+                #[cfg(feature = "metrics")]
+                if guard.is_none() {
+                    crate::metrics::METRICS.record_timeout();
+                }
+
+                guard
+            }
             None => Some(self.inner.lock()),
         }
-        .map(|guard| SafeHandleGuard { _guard: guard })
+        .map(|guard| self.guard(guard))
+    }
+
+    /// Acquires the handle only if it is free right now, without waiting or counting a timeout.
+    fn try_acquire(&self) -> Option<SafeHandleGuard<'_>> {
+        self.inner.try_lock().map(|guard| self.guard(guard))
+    }
+
+    /// Records the acquisition of the handle's mutex and wraps it in a `SafeHandleGuard`.
+    fn guard<'a>(&'a self, guard: MutexGuard<'a, Arc<RawHandle>>) -> SafeHandleGuard<'a> {
+        let acquired_at = Instant::now();
+        *self.acquired_at.lock() = Some(acquired_at);
+
+        SafeHandleGuard {
+            _guard: guard,
+            acquired_at,
+            holder: &self.acquired_at,
+        }
+    }
+
+    /// Returns how long the current holder has had the handle, or `None` if it isn't held.
+    ///
+    /// Useful for finding the operation holding the handle when others time out acquiring it.
+    pub fn held_duration(&self) -> Option<Duration> {
+        self.acquired_at.lock().map(|acquired_at| acquired_at.elapsed())
+    }
+
+    /// Acquires the handle once and runs a closure with it, so several raw WinAPI calls share one acquisition.
+    ///
+    /// Unlike `with_handle!`, the closure can be any function, and `?` inside it returns from the closure.
+    /// The crate's own read/write functions acquire the handle themselves, so calling them inside the
+    /// closure blocks until `timeout` (or forever without one); use raw WinAPI calls on the guard instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Optional timeout for acquiring the handle
+    /// * `f` - Closure receiving the guard
+    ///
+    /// # Examples
+    ///
+    /// ```rust,norun
+    /// let (a, b) = handle.lock_scope(Some(Duration::from_secs(1)), |guard| {
+    ///     let a = raw_read(**guard, addr_a)?;
+    ///     let b = raw_read(**guard, addr_b)?;
+    ///     Ok((a, b))
+    /// })?;
+    /// ```
+    pub fn lock_scope<T, F>(&self, timeout: Option<Duration>, f: F) -> MemOpResult<T>
+    where
+        F: FnOnce(&SafeHandleGuard<'_>) -> MemOpResult<T>,
+    {
+        match self.acquire_with_timeout(timeout) {
+            Some(guard) => f(&guard),
+            None => Err(MemOpError::TimeoutReached((
+                timeout,
+                Some(anyhow::anyhow!("SafeHandle::lock_scope()")),
+            ))),
+        }
     }
 }
 
@@ -230,4 +421,67 @@ impl<'a> std::ops::Deref for SafeHandleGuard<'a> {
     fn deref(&self) -> &Self::Target {
         &**self._guard
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use windows_result::HRESULT;
+
+    /// Spawns a `cmd` that runs until its stdin is closed.
+    fn spawn_child() -> std::process::Child {
+        std::process::Command::new("cmd")
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap()
+    }
+
+    /// Closes the child's stdin and waits for it to exit.
+    fn exit_child(child: &mut std::process::Child) {
+        drop(child.stdin.take());
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn is_alive_child_until_exit() {
+        let mut child = spawn_child();
+        let handle = open_process(child.id()).unwrap();
+        assert!(is_alive(&handle));
+
+        exit_child(&mut child);
+        assert!(!is_alive(&handle));
+    }
+
+    #[test]
+    fn read_dead_child_process_exited() {
+        let mut child = spawn_child();
+        let handle = open_process(child.id()).unwrap();
+        exit_child(&mut child);
+
+        let timeout = Some(Duration::from_secs(1));
+        let result = crate::memory::read::read_bytes(&handle, 0x10000, 0x4, timeout);
+
+        assert!(matches!(result, Err(MemOpError::ProcessExited)));
+    }
+
+    #[test]
+    fn exited_or_does_not_wait_for_a_held_handle() {
+        let mut child = spawn_child();
+        let handle = open_process(child.id()).unwrap();
+        exit_child(&mut child);
+
+        let _guard = handle.acquire_with_timeout(None).unwrap();
+        let err = std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    // ERROR_PARTIAL_COPY
+                    let partial_copy = windows_result::Error::from_hresult(HRESULT::from_win32(299));
+                    exited_or(&handle, MemOpError::WinAPI((partial_copy, None)))
+                })
+                .join()
+                .unwrap()
+        });
+
+        assert!(!matches!(err, MemOpError::ProcessExited));
+    }
+}
@@ -1,4 +1,3 @@
-use anyhow::{anyhow, Result};
 use std::time::Duration;
 
 use windows::Win32::Foundation::HMODULE;
@@ -6,10 +5,24 @@ use windows::Win32::System::ProcessStatus::{
     EnumProcessModules, GetModuleFileNameExA, GetModuleInformation, MODULEINFO,
 };
 
-use crate::process::SafeHandle;
-use crate::with_handle;
+use crate::memory::read::{read_bytes, read_committed};
+use crate::memory::write::write_bytes;
+use crate::process::pe::{self, ExportDirectory, SectionInfo};
+use crate::process::{exited_or, SafeHandle};
+use crate::{memop_err, with_handle, MemOpError, MemOpResult};
 
 
+/// A module's full path, handle and information, as returned by `get_named_modules()`.
+pub type NamedModule = (String, HMODULE, MODULEINFO);
+
+/// Result of `get_named_modules_reporting()`.
+pub struct ModuleEnumeration {
+    /// The modules as returned by `get_named_modules()`
+    pub modules: Vec<NamedModule>,
+    /// Modules whose info could not be fetched, with the error fetching it
+    pub skipped: Vec<(HMODULE, MemOpError)>,
+}
+
 /// Retrieves the name and associated information for all modules in a given process.
 /// 
 /// # Arguments
@@ -34,7 +47,19 @@ use crate::with_handle;
 pub fn get_named_modules(
     handle: &SafeHandle,
     timeout: Option<Duration>,
-) -> Result<Vec<(String, HMODULE, MODULEINFO)>> {
+) -> MemOpResult<Vec<(String, HMODULE, MODULEINFO)>> {
+    Ok(get_named_modules_reporting(handle, timeout)?.modules)
+}
+
+/// Same as `get_named_modules()`, but also returns the modules that were skipped.
+///
+/// A module unloading between its enumeration and the info fetch makes `GetModuleInformation` fail.
+/// Such modules are skipped rather than failing the whole enumeration, and returned with their error.
+/// Timeouts and the process exiting still fail the enumeration.
+pub fn get_named_modules_reporting(
+    handle: &SafeHandle,
+    timeout: Option<Duration>,
+) -> MemOpResult<ModuleEnumeration> {
     let mut modules = Vec::with_capacity(1024);
     let mut bytes_needed = 0;
 
@@ -54,6 +79,7 @@ pub fn get_named_modules(
     })?;
 
     let mut module_names: Vec<(String, HMODULE, MODULEINFO)> = Vec::with_capacity(modules.len());
+    let mut skipped: Vec<(HMODULE, MemOpError)> = Vec::new();
 
     for &module in &modules {
         let mut name_raw = [0u8; 260];
@@ -64,7 +90,16 @@ pub fn get_named_modules(
             }
         })?;
 
-        let info: MODULEINFO = get_module_info(handle, module, timeout)?;
+        let info: MODULEINFO = match get_module_info(handle, module, timeout) {
+            Ok(info) => info,
+            Err(e) => match exited_or(handle, e) {
+                e if e.is_winapi() => {
+                    skipped.push((module, e));
+                    continue;
+                }
+                e => return Err(e),
+            },
+        };
 
         if length > 0 {
             if let Ok(name) = String::from_utf8(
@@ -79,7 +114,10 @@ pub fn get_named_modules(
         }
     }
 
-    Ok(module_names)
+    Ok(ModuleEnumeration {
+        modules: module_names,
+        skipped,
+    })
 }
 
 /// Retrieves module location information from a given module.
@@ -107,7 +145,7 @@ pub fn get_module_info(
     handle: &SafeHandle,
     module: HMODULE,
     timeout: Option<Duration>,
-) -> Result<MODULEINFO> {
+) -> MemOpResult<MODULEINFO> {
     let mut info = MODULEINFO::default();
 
     with_handle!(handle, timeout, |guard| -> (), {
@@ -153,15 +191,8 @@ pub fn module_by_name(
     name: &str,
     stem: bool,
     timeout: Option<Duration>,
-) -> Result<Option<HMODULE>> {
-    let modules = match get_named_modules(handle, timeout) {
-        Ok(m) => m,
-        Err(e) => {
-            return Err(anyhow!(
-                "Obtaining module with name \"{name}\" failed with the following error: \"{e}\""
-            ))
-        }
-    };
+) -> MemOpResult<Option<HMODULE>> {
+    let modules = get_named_modules(handle, timeout)?;
 
     for (mut module_name, module, _) in modules {
         if stem {
@@ -179,3 +210,263 @@ pub fn module_by_name(
 
     Ok(None)
 }
+
+/// Reads the PE headers of a module loaded in a given process.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `module` - Handle to the module whose headers should be read
+/// * `timeout` - Optional timeout duration for the operation
+///
+/// # Returns
+/// Returns the first `pe::HEADERS_SIZE` bytes of the module image
+pub fn read_module_headers(
+    handle: &SafeHandle,
+    module: HMODULE,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<u8>> {
+    read_bytes(handle, module.0 as usize, pe::HEADERS_SIZE, timeout)
+}
+
+/// Reads a module's whole image, region by region.
+///
+/// Uncommitted or unreadable pages are zero-filled instead of failing the read, so this works
+/// for images with gaps where a single `read_bytes()` over `SizeOfImage` would not.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `module` - Handle to the module to read
+///
+/// # Returns
+/// Returns `SizeOfImage` bytes, indexed by RVA
+pub fn read_module_image(handle: &SafeHandle, module: HMODULE) -> MemOpResult<Vec<u8>> {
+    let info = get_module_info(handle, module, None)?;
+
+    read_committed(handle, info.lpBaseOfDll as usize, info.SizeOfImage as usize, None)
+}
+
+/// Reads and parses the section table of a module loaded in a given process.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `module` - Handle to the module whose sections should be read
+/// * `timeout` - Optional timeout duration for the operation
+///
+/// # Returns
+/// Returns every section of the module, with RVAs relative to its base
+pub fn get_module_sections(
+    handle: &SafeHandle,
+    module: HMODULE,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<SectionInfo>> {
+    let headers = read_module_headers(handle, module, timeout)?;
+    pe::sections(&headers)
+}
+
+/// Reads and parses the export directory of a module loaded in a given process.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `module` - Handle to the module whose exports should be read
+/// * `timeout` - Optional timeout duration for the operation
+///
+/// # Returns
+/// Returns the parsed export directory, errors if the module has no exports
+pub fn get_export_directory(
+    handle: &SafeHandle,
+    module: HMODULE,
+    timeout: Option<Duration>,
+) -> MemOpResult<ExportDirectory> {
+    let headers = read_module_headers(handle, module, timeout)?;
+    let (rva, size) = pe::data_directory(&headers, pe::IMAGE_DIRECTORY_ENTRY_EXPORT)?;
+
+    if rva == 0 || size == 0 {
+        return Err(crate::memop_err!("Module has no export directory."));
+    }
+
+    let bytes = read_bytes(handle, module.0 as usize + rva, size, timeout)?;
+    ExportDirectory::parse(rva, bytes)
+}
+
+/// Resolves the address of a function exported by name from a module loaded in a given process.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `module` - Handle to the module exporting the function
+/// * `name` - The exported name of the function
+/// * `timeout` - Optional timeout duration for the operation
+///
+/// # Returns
+/// Returns the absolute address of the exported function
+///
+/// # Example
+/// ```rust,norun
+/// let process_handle = get_process_handle(process_id)?;
+/// if let Some(kernel32) = module_by_name(&process_handle, "kernel32.dll", true, None)? {
+///     let addr = get_export_address(&process_handle, kernel32, "Sleep", None)?;
+///     println!("Sleep is at {:#X}", addr);
+/// }
+/// ```
+pub fn get_export_address(
+    handle: &SafeHandle,
+    module: HMODULE,
+    name: &str,
+    timeout: Option<Duration>,
+) -> MemOpResult<usize> {
+    let exports = get_export_directory(handle, module, timeout)?;
+
+    Ok(module.0 as usize + exports.rva_by_name(name)?)
+}
+
+/// Resolves the address of a function exported by ordinal from a module loaded in a given process.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `module` - Handle to the module exporting the function
+/// * `ordinal` - The export ordinal, as listed by tools like dumpbin (already including the ordinal base)
+/// * `timeout` - Optional timeout duration for the operation
+///
+/// # Returns
+/// Returns the absolute address of the exported function
+pub fn get_export_by_ordinal(
+    handle: &SafeHandle,
+    module: HMODULE,
+    ordinal: u32,
+    timeout: Option<Duration>,
+) -> MemOpResult<usize> {
+    let exports = get_export_directory(handle, module, timeout)?;
+
+    Ok(module.0 as usize + exports.rva_by_ordinal(ordinal)?)
+}
+
+/// Reads the import table of a module loaded in a given process.
+///
+/// The module image is read whole, as import names are spread across it. See `pe::imports()`.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `module` - Handle to the module whose imports should be read
+///
+/// # Returns
+/// Returns each imported DLL name with the names of the functions imported from it
+///
+/// # Example
+/// ```rust,norun
+/// for (dll, functions) in get_imports(&process_handle, module)? {
+///     println!("{dll}: {}", functions.join(", "));
+/// }
+/// ```
+pub fn get_imports(handle: &SafeHandle, module: HMODULE) -> MemOpResult<Vec<(String, Vec<String>)>> {
+    let image = read_module_image(handle, module)?;
+    pe::imports(&image)
+}
+
+/// Converts an RVA into a virtual address within a module loaded at `module_base`.
+pub fn rva_to_va(module_base: usize, rva: usize) -> usize {
+    module_base + rva
+}
+
+/// Converts a virtual address into an RVA relative to `module_base`, erroring if it lies below the base.
+pub fn va_to_rva(module_base: usize, va: usize) -> MemOpResult<usize> {
+    va.checked_sub(module_base).ok_or(memop_err!(
+        "Address \"{va:#X}\" lies below module base \"{module_base:#X}\"."
+    ))
+}
+
+/// Converts an offset into a module's file into an RVA, using the section containing it.
+///
+/// # Arguments
+/// * `sections` - Sections of the module, e.g. from `get_module_sections()`
+/// * `file_offset` - Offset into the module's file on disk
+///
+/// # Returns
+/// Returns the RVA the byte at `file_offset` is mapped to, errors if no section's raw data contains it
+pub fn file_offset_to_rva(sections: &[SectionInfo], file_offset: usize) -> MemOpResult<usize> {
+    sections
+        .iter()
+        .find(|s| (s.raw_offset..s.raw_offset + s.raw_size).contains(&file_offset))
+        .map(|s| s.virtual_address + (file_offset - s.raw_offset))
+        .ok_or(memop_err!(
+            "File offset \"{file_offset:#X}\" does not lie in the raw data of any section."
+        ))
+}
+
+/// Reads bytes at an RVA of a module loaded in a given process.
+///
+/// # Example
+/// ```rust,norun
+/// let bytes = read_rva(&process_handle, module, 0x1A2B30, 0x10, None)?;
+/// ```
+pub fn read_rva(
+    handle: &SafeHandle,
+    module: HMODULE,
+    rva: usize,
+    size: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<u8>> {
+    read_bytes(handle, rva_to_va(module.0 as usize, rva), size, timeout)
+}
+
+/// Writes bytes at an RVA of a module loaded in a given process.
+///
+/// # Example
+/// ```rust,norun
+/// write_rva(&process_handle, module, 0x1A2B30, &[0x90, 0x90], None)?;
+/// ```
+pub fn write_rva(
+    handle: &SafeHandle,
+    module: HMODULE,
+    rva: usize,
+    bytes: &[u8],
+    timeout: Option<Duration>,
+) -> MemOpResult<()> {
+    write_bytes(handle, rva_to_va(module.0 as usize, rva), bytes, timeout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(name: &str, rva: usize, raw_offset: usize, raw_size: usize) -> SectionInfo {
+        SectionInfo {
+            name: name.to_string(),
+            virtual_address: rva,
+            virtual_size: raw_size,
+            raw_size,
+            raw_offset,
+            characteristics: 0,
+        }
+    }
+
+    #[test]
+    fn address_conversions_agree_across_sections() {
+        let base = 0x400000;
+        let sections = [
+            section(".text", 0x1000, 0x400, 0x600),
+            section(".data", 0x3000, 0xA00, 0x200),
+        ];
+
+        // (file offset, RVA, VA) of the first and last byte of each section
+        let layout = [
+            (0x400, 0x1000, 0x401000),
+            (0x9FF, 0x15FF, 0x4015FF),
+            (0xA00, 0x3000, 0x403000),
+            (0xBFF, 0x31FF, 0x4031FF),
+        ];
+
+        for (file_offset, rva, va) in layout {
+            assert_eq!(file_offset_to_rva(&sections, file_offset).unwrap(), rva);
+            assert_eq!(rva_to_va(base, rva), va);
+            assert_eq!(va_to_rva(base, va).unwrap(), rva);
+        }
+    }
+
+    #[test]
+    fn address_conversions_reject_addresses_outside_the_module() {
+        let sections = [section(".text", 0x1000, 0x400, 0x600)];
+
+        assert!(file_offset_to_rva(&sections, 0x3FF).is_err());
+        assert!(file_offset_to_rva(&sections, 0xA00).is_err());
+        assert!(va_to_rva(0x400000, 0x3FFFFF).is_err());
+    }
+}
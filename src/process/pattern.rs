@@ -1,4 +1,6 @@
 use crate::memory::Byte;
+use crate::process::pe::SectionInfo;
+use crate::{memop_err, MemOpResult};
 use anyhow::Result;
 
 /// Top-level function for performing memory AOB scans.
@@ -8,20 +10,442 @@ use anyhow::Result;
 /// * `pattern`: IDA byte pattern
 /// * `origin`: Address to begin searching at
 /// * `size`: Size (in bytes) of search area
+/// * `limit`: Stop after this many matches, `None` finds all
 /// # Returns
 /// * `anyhow::Result<Vec<(usize, Vec<u8>)>>`: Anyhow result of a vector of addresses where a match was found, including bytes found at matches
 pub fn pattern_scan(
     handle: &crate::process::SafeHandle,
-    pattern: &str,
+    pattern: impl Into<Pattern>,
     origin: usize,
     size: usize,
+    limit: Option<usize>,
 ) -> anyhow::Result<Vec<(usize, Vec<u8>)>> {
-    use crate::process::pattern::{find_pattern_in_bytes, prepare_pattern};
+    let pattern = pattern.into();
 
+    let scan_size = size - 0x04;
+    if scan_size > CHUNKED_SCAN_THRESHOLD {
+        return Ok(pattern_scan_chunked(handle, pattern, origin, scan_size, SCAN_WINDOW_SIZE, limit)?);
+    }
+
+    let bytes = crate::memory::read::read_bytes(handle, origin, scan_size, None)?;
+    find_pattern_in_bytes(bytes, pattern, limit)
+}
+
+/// Search areas larger than this are scanned by `pattern_scan()` in windows, rather than read at once.
+pub const CHUNKED_SCAN_THRESHOLD: usize = 0x400_0000;
+
+/// Window size used by `pattern_scan()` for search areas above `CHUNKED_SCAN_THRESHOLD`.
+pub const SCAN_WINDOW_SIZE: usize = 0x100_0000;
+
+/// Performs a memory AOB scan, reading the search area one window at a time so memory use stays bounded.
+///
+/// Consecutive windows overlap by the pattern length, so matches straddling a window boundary are found.
+///
+/// # Arguments
+/// * `handle`: WinAPI handle (*mut c_void) with PROCESS_VM_READ
+/// * `pattern`: IDA byte pattern
+/// * `origin`: Address to begin searching at
+/// * `size`: Size (in bytes) of search area
+/// * `window_size`: Amount of bytes scanned per read
+/// * `limit`: Stop after this many matches, `None` finds all
+/// # Returns
+/// * `MemOpResult<Vec<(usize, Vec<u8>)>>`: Offsets of matches relative to `origin`, with the bytes found at each
+pub fn pattern_scan_chunked(
+    handle: &crate::process::SafeHandle,
+    pattern: impl Into<Pattern>,
+    origin: usize,
+    size: usize,
+    window_size: usize,
+    limit: Option<usize>,
+) -> MemOpResult<Vec<(usize, Vec<u8>)>> {
+    let pattern_bytes = pattern.into();
+
+    find_pattern_chunked(pattern_bytes.bytes(), size, window_size, limit, |offset, len| {
+        crate::memory::read::read_bytes(handle, origin + offset, len, None)
+    })
+}
+
+/// Finds all matches of a pattern in `size` bytes supplied one window at a time by `read_window`.
+///
+/// # Arguments
+/// * `pattern`: Optional bytes to find
+/// * `size`: Total amount of bytes to search
+/// * `window_size`: Amount of bytes searched per window, extended by the pattern length minus one
+/// * `limit`: Stop after this many matches without reading further windows, `None` finds all
+/// * `read_window`: Returns `len` bytes starting at `offset` of the searched area
+/// # Returns
+/// * `MemOpResult<Vec<(usize, Vec<u8>)>>`: Offsets of every match in order, with the bytes found at each
+pub fn find_pattern_chunked(
+    pattern: &[Byte],
+    size: usize,
+    window_size: usize,
+    limit: Option<usize>,
+    mut read_window: impl FnMut(usize, usize) -> MemOpResult<Vec<u8>>,
+) -> MemOpResult<Vec<(usize, Vec<u8>)>> {
+    if window_size == 0 {
+        return Err(memop_err!("Window size of a chunked scan must not be zero."));
+    }
+
+    let pattern_length = pattern.len();
+    let limit = limit.unwrap_or(usize::MAX);
+    let mut matches = Vec::new();
+    let mut start = 0;
+
+    while start < size && matches.len() < limit {
+        // Extend the window so a match beginning in it is always fully contained.
+        let len = (window_size + pattern_length.saturating_sub(1)).min(size - start);
+        let bytes = read_window(start, len)?;
+
+        // Matches beginning past the window are found (once) by the next window instead.
+        let window_matches = pattern_offsets(&bytes, pattern)
+            .take_while(|&offset| offset < window_size)
+            .take(limit - matches.len())
+            .map(|offset| (start + offset, bytes[offset..offset + pattern_length].to_vec()));
+        matches.extend(window_matches);
+
+        start += window_size;
+    }
+
+    Ok(matches)
+}
+
+/// Performs a memory AOB scan, returning each match with its surrounding bytes.
+///
+/// # Arguments
+/// * `handle`: WinAPI handle (*mut c_void) with PROCESS_VM_READ
+/// * `pattern`: IDA byte pattern
+/// * `origin`: Address to begin searching at
+/// * `size`: Size (in bytes) of search area
+/// * `context_before`: Amount of bytes to include before each match
+/// * `context_after`: Amount of bytes to include after each match
+/// # Returns
+/// * `MemOpResult<Vec<(usize, Vec<u8>)>>`: Offsets of matches relative to `origin`, with the matched bytes and their context, clamped to the search area
+pub fn pattern_scan_context(
+    handle: &crate::process::SafeHandle,
+    pattern: impl Into<Pattern>,
+    origin: usize,
+    size: usize,
+    context_before: usize,
+    context_after: usize,
+) -> MemOpResult<Vec<(usize, Vec<u8>)>> {
+    let bytes = crate::memory::read::read_bytes(handle, origin, size, None)?;
+
+    Ok(find_pattern_context_in_bytes(&bytes, pattern.into().bytes(), context_before, context_after))
+}
+
+/// Finds all matches of a pattern in a buffer, returning each with its surrounding bytes.
+///
+/// # Arguments
+/// * `bytes`: Bytes to search
+/// * `pattern`: Optional bytes to find
+/// * `context_before`: Amount of bytes to include before each match
+/// * `context_after`: Amount of bytes to include after each match
+/// # Returns
+/// * `Vec<(usize, Vec<u8>)>`: Offsets of matches, with the matched bytes and their context clamped to `bytes`
+pub fn find_pattern_context_in_bytes(
+    bytes: &[u8],
+    pattern: &[Byte],
+    context_before: usize,
+    context_after: usize,
+) -> Vec<(usize, Vec<u8>)> {
+    pattern_offsets(bytes, pattern)
+        .map(|offset| {
+            let start = offset.saturating_sub(context_before);
+            let end = offset
+                .saturating_add(pattern.len())
+                .saturating_add(context_after)
+                .min(bytes.len());
+            (offset, bytes[start..end].to_vec())
+        })
+        .collect()
+}
+
+/// Scans a single named section of an already-read module image.
+///
+/// # Arguments
+/// * `bytes`: The module as mapped in memory, read from its base
+/// * `sections`: Sections of the module, e.g. from `get_module_sections()`
+/// * `section_name`: Name of the section to scan, e.g. `.text`
+/// * `pattern`: IDA byte pattern
+/// # Returns
+/// * `MemOpResult<Vec<usize>>`: RVAs of every match within the section
+pub fn scan_module_buffer(
+    bytes: &[u8],
+    sections: &[SectionInfo],
+    section_name: &str,
+    pattern: impl Into<Pattern>,
+) -> MemOpResult<Vec<usize>> {
+    let section = sections
+        .iter()
+        .find(|s| s.name == section_name)
+        .ok_or(memop_err!("Module has no section named \"{section_name}\"."))?;
+
+    let range = section.rva_range();
+    let start = range.start.min(bytes.len());
+    let end = range.end.min(bytes.len());
+
+    let matches = find_pattern_offsets(&bytes[start..end], pattern.into().bytes());
 
-    let bytes = crate::memory::read::read_bytes(handle, origin, size - 0x04, None)?;
-    let pattern_bytes = prepare_pattern(pattern);
-    find_pattern_in_bytes(bytes, pattern_bytes)
+    Ok(matches.into_iter().map(|offset| start + offset).collect())
+}
+
+/// Group name used by `pattern_scan_all_by_module()` for matches outside of any module (heap, stacks, etc).
+pub const UNNAMED_REGION_GROUP: &str = "<unnamed>";
+
+/// Scans every readable region of a process, grouping matches by the module containing them.
+///
+/// Regions are scanned one at a time, so a match spanning two regions is not found.
+///
+/// # Arguments
+/// * `handle`: A safe handle to the target process
+/// * `pattern`: IDA byte pattern
+/// # Returns
+/// * `MemOpResult<Vec<(String, Vec<usize>)>>`: Module file names (in load order) with the absolute addresses
+///   of their matches, followed by `UNNAMED_REGION_GROUP` for matches outside of any module. Groups without
+///   matches are left out.
+pub fn pattern_scan_all_by_module(
+    handle: &crate::process::SafeHandle,
+    pattern: impl Into<Pattern>,
+) -> MemOpResult<Vec<(String, Vec<usize>)>> {
+    use crate::memory::utils::readable_regions;
+    use crate::process::module::get_named_modules;
+
+    let pattern_bytes = pattern.into();
+
+    let modules: Vec<(String, std::ops::Range<usize>)> = get_named_modules(handle, None)?
+        .into_iter()
+        .map(|(path, _, info)| {
+            let name = path.rsplit(['\\', '/']).next().unwrap_or(&path).to_string();
+            let base = info.lpBaseOfDll as usize;
+            (name, base..base + info.SizeOfImage as usize)
+        })
+        .collect();
+    let mut matches: Vec<usize> = Vec::new();
+
+    for region in readable_regions(handle, None)? {
+        let base = region.BaseAddress as usize;
+
+        // Regions can be freed or reprotected between the walk and the read, skip those.
+        let Ok(bytes) = crate::memory::read::read_bytes(handle, base, region.RegionSize, None) else {
+            continue;
+        };
+
+        matches.extend(
+            find_pattern_offsets(&bytes, pattern_bytes.bytes())
+                .into_iter()
+                .map(|offset| base + offset),
+        );
+    }
+
+    Ok(group_by_module(&modules, matches))
+}
+
+/// Groups addresses by the module containing them, see `pattern_scan_all_by_module()`.
+///
+/// # Arguments
+/// * `modules`: Module names (in load order) with the address range of their image
+/// * `addrs`: Addresses to group
+/// # Returns
+/// * `Vec<(String, Vec<usize>)>`: Module names with their addresses, followed by `UNNAMED_REGION_GROUP`
+///   for addresses outside of any module. Groups without addresses are left out.
+pub fn group_by_module(
+    modules: &[(String, std::ops::Range<usize>)],
+    addrs: impl IntoIterator<Item = usize>,
+) -> Vec<(String, Vec<usize>)> {
+    let mut groups: Vec<Vec<usize>> = vec![Vec::new(); modules.len()];
+    let mut unnamed: Vec<usize> = Vec::new();
+
+    for addr in addrs {
+        match modules.iter().position(|(_, range)| range.contains(&addr)) {
+            Some(index) => groups[index].push(addr),
+            None => unnamed.push(addr),
+        }
+    }
+
+    let mut result: Vec<(String, Vec<usize>)> = modules
+        .iter()
+        .zip(groups)
+        .filter(|(_, addrs)| !addrs.is_empty())
+        .map(|((name, _), addrs)| (name.clone(), addrs))
+        .collect();
+
+    if !unnamed.is_empty() {
+        result.push((UNNAMED_REGION_GROUP.to_string(), unnamed));
+    }
+
+    result
+}
+
+/// How the address of interest is derived from where a signature matched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureAnchor {
+    /// The match address plus an offset, for values located at the match itself
+    Offset(isize),
+    /// A rel32 displacement at `disp_offset` from the match, relative to the end of its
+    /// `instr_len`-byte instruction, as in `call`/`jmp` or x64 RIP-relative operands
+    Relative { disp_offset: usize, instr_len: usize },
+    /// An absolute 32-bit address embedded at an offset from the match, as in x86 `mov eax, [addr]`
+    Absolute32(usize),
+}
+
+/// Scans a module for a signature and resolves the address its anchor points at.
+///
+/// Only the first match is used. Anchors are resolved from the scanned image, without further reads.
+///
+/// # Arguments
+/// * `handle`: A safe handle to the target process
+/// * `module`: The module to scan
+/// * `pattern`: IDA byte pattern
+/// * `anchor`: How to derive the address from the match
+/// # Returns
+/// * `MemOpResult<usize>`: The resolved address, or `MemOpError::PatternNotFound` if nothing matched
+pub fn resolve_signature(
+    handle: &crate::process::SafeHandle,
+    module: windows::Win32::Foundation::HMODULE,
+    pattern: impl Into<Pattern>,
+    anchor: SignatureAnchor,
+) -> MemOpResult<usize> {
+    let image = crate::process::module::read_module_image(handle, module)?;
+
+    resolve_signature_in_image(&image, module.0 as usize, pattern, anchor)
+}
+
+/// Resolves a signature against a module image already read, see `resolve_signature()`.
+///
+/// # Arguments
+/// * `image`: The module's image, as laid out in memory
+/// * `base`: Address the module is loaded at
+/// * `pattern`: IDA byte pattern
+/// * `anchor`: How to derive the address from the match
+pub fn resolve_signature_in_image(
+    image: &[u8],
+    base: usize,
+    pattern: impl Into<Pattern>,
+    anchor: SignatureAnchor,
+) -> MemOpResult<usize> {
+    use crate::process::pe::read_u32;
+
+    let rva = *find_pattern_offsets(image, pattern.into().bytes())
+        .first()
+        .ok_or(crate::MemOpError::PatternNotFound)?;
+
+    match anchor {
+        SignatureAnchor::Offset(offset) => Ok((base + rva).wrapping_add_signed(offset)),
+        SignatureAnchor::Relative { disp_offset, instr_len } => {
+            let disp = read_u32(image, rva + disp_offset)? as i32;
+            Ok((base + rva + instr_len).wrapping_add_signed(disp as isize))
+        }
+        SignatureAnchor::Absolute32(offset) => Ok(read_u32(image, rva + offset)? as usize),
+    }
+}
+
+/// Reads a value at the address a signature resolves to, see `resolve_signature()`.
+///
+/// # Example
+/// ```rust,norun
+/// // mov eax, [player_count] ; A1 ?? ?? ?? ??
+/// let count = read_at_signature::<i32>(&handle, module, "A1 ?? ?? ?? ?? 85 C0", SignatureAnchor::Absolute32(1), None)?;
+/// ```
+pub fn read_at_signature<T: bytemuck::Pod>(
+    handle: &crate::process::SafeHandle,
+    module: windows::Win32::Foundation::HMODULE,
+    pattern: impl Into<Pattern>,
+    anchor: SignatureAnchor,
+    timeout: Option<std::time::Duration>,
+) -> MemOpResult<T> {
+    let addr = resolve_signature(handle, module, pattern, anchor)?;
+    let bytes = crate::memory::read::read_bytes(handle, addr, std::mem::size_of::<T>(), timeout)?;
+
+    bytemuck::try_pod_read_unaligned::<T>(&bytes).map_err(|e| {
+        memop_err!(
+            "Failed to convert bytes at \"{addr:#X}\" to \"{}\": {e}",
+            std::any::type_name::<T>()
+        )
+    })
+}
+
+/// A byte pattern accepted by the scan functions, convertible from the forms patterns come in.
+///
+/// * `&str`: IDA byte pattern, see `prepare_pattern()`
+/// * `&[Byte]`/`Vec<Byte>`: Optional bytes, where `None` is a wildcard
+/// * `(&[u8], &str)`: Bytes with a code-style mask, where `?` is a wildcard and any other character
+///   (conventionally `x`) must match; bytes past the end of the mask must match
+///
+/// # Example
+/// ```rust,norun
+/// let ida = Pattern::from("8B ?? 85");
+/// let masked = Pattern::from((&[0x8B, 0x00, 0x85][..], "x?x"));
+/// assert_eq!(ida, masked);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pattern(Vec<Byte>);
+
+impl Pattern {
+    /// Returns the pattern's optional bytes.
+    pub fn bytes(&self) -> &[Byte] {
+        &self.0
+    }
+
+    /// Returns the pattern's optional bytes, consuming it.
+    pub fn into_bytes(self) -> Vec<Byte> {
+        self.0
+    }
+
+    /// Returns the length of the pattern in bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the pattern has no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl AsRef<[Byte]> for Pattern {
+    fn as_ref(&self) -> &[Byte] {
+        &self.0
+    }
+}
+
+impl From<&str> for Pattern {
+    fn from(pattern: &str) -> Self {
+        Pattern(prepare_pattern(pattern))
+    }
+}
+
+impl From<&String> for Pattern {
+    fn from(pattern: &String) -> Self {
+        Pattern(prepare_pattern(pattern))
+    }
+}
+
+impl From<&[Byte]> for Pattern {
+    fn from(pattern: &[Byte]) -> Self {
+        Pattern(pattern.to_vec())
+    }
+}
+
+impl From<Vec<Byte>> for Pattern {
+    fn from(pattern: Vec<Byte>) -> Self {
+        Pattern(pattern)
+    }
+}
+
+impl From<(&[u8], &str)> for Pattern {
+    fn from((bytes, mask): (&[u8], &str)) -> Self {
+        let mut mask = mask.chars();
+
+        Pattern(
+            bytes
+                .iter()
+                .map(|&byte| match mask.next() {
+                    Some('?') => None,
+                    _ => Some(byte),
+                })
+                .collect(),
+        )
+    }
 }
 
 /// Turns a pattern into a vector of Option<u8>.
@@ -54,35 +478,53 @@ fn byte_matches(byte: &u8, pattern: Byte) -> bool {
     };
 }
 
-/// Finds all matches of a given pattern in a byte vector.
+/// Finds the offsets of all matches of a given pattern, without copying the matched bytes.
 ///
 /// # Arguments
-/// * `bytes`: Vector of bytes to search
-/// * `pattern`: Vec of optional bytes to find
+/// * `bytes`: Bytes to search
+/// * `pattern`: Optional bytes to find
 /// # Returns
-/// * `anyhow::Result<Vec<(usize, Vec<usize>)>>`: Vector of addresses relative to the provided bytes, with a byte vector of the bytes found at the pattern.
-pub fn find_pattern_in_bytes(bytes: Vec<u8>, pattern: Vec<Byte>) -> Result<Vec<(usize, Vec<u8>)>> {
-    let pattern_length = pattern.len();
-    let mut matches: Vec<(usize, Vec<u8>)> = Vec::new();
+/// * `Vec<usize>`: Offsets of every match relative to the provided bytes
+pub fn find_pattern_offsets(bytes: &[u8], pattern: &[Byte]) -> Vec<usize> {
+    pattern_offsets(bytes, pattern).collect()
+}
 
+/// Lazily yields the offsets of matches in ascending order, so callers can stop early.
+fn pattern_offsets<'a>(bytes: &'a [u8], pattern: &'a [Byte]) -> impl Iterator<Item = usize> + 'a {
     // Only iterate up to where a full pattern could still fit
-    for i in 0..=bytes.len().saturating_sub(pattern_length) {
-        let mut match_found = true;
-
-        // Compare each byte in the pattern
-        for (j, pattern_byte) in pattern.iter().copied().enumerate() {
-            if !byte_matches(&bytes[i + j], pattern_byte) {
-                match_found = false;
-                break;
-            }
-        }
+    let last_start = (bytes.len() + 1).saturating_sub(pattern.len());
 
-        if match_found {
-            matches.push((i, Vec::from(&bytes[i..i + pattern_length])));
-        }
-    }
+    (0..last_start).filter(move |&i| {
+        pattern
+            .iter()
+            .copied()
+            .enumerate()
+            .all(|(j, pattern_byte)| byte_matches(&bytes[i + j], pattern_byte))
+    })
+}
 
-    Ok(matches)
+/// Finds all matches of a given pattern in a byte vector.
+///
+/// Prefer `find_pattern_offsets()` when the matched bytes aren't needed.
+///
+/// # Arguments
+/// * `bytes`: Vector of bytes to search
+/// * `pattern`: Pattern to find, e.g. a `Vec<Byte>`
+/// * `limit`: Stop after this many matches, `None` finds all
+/// # Returns
+/// * `anyhow::Result<Vec<(usize, Vec<usize>)>>`: Vector of addresses relative to the provided bytes, in order, with a byte vector of the bytes found at the pattern.
+pub fn find_pattern_in_bytes(
+    bytes: Vec<u8>,
+    pattern: impl Into<Pattern>,
+    limit: Option<usize>,
+) -> Result<Vec<(usize, Vec<u8>)>> {
+    let pattern = pattern.into();
+    let pattern_length = pattern.len();
+
+    Ok(pattern_offsets(&bytes, pattern.bytes())
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|i| (i, Vec::from(&bytes[i..i + pattern_length])))
+        .collect())
 }
 
 pub fn create_unhook_bytes(pattern: &[Byte], found_bytes: &[u8]) -> Vec<u8> {
@@ -99,6 +541,20 @@ pub fn create_unhook_bytes(pattern: &[Byte], found_bytes: &[u8]) -> Vec<u8> {
     return unhook_bytes;
 }
 
+/// Parses an IDA-style byte pattern into a `&'static [Byte]` at compile time.
+///
+/// Bytes are two hex digits and wildcards are `?` or `??`, separated by any amount of whitespace.
+/// Malformed or empty patterns fail to compile with a message naming the issue.
+///
+/// # Example
+/// ```rust,norun
+/// let pattern: &'static [Byte] = byte_pattern!("8B 45 ?? 89 ? 08");
+/// ```
+///
+/// A byte that isn't two hex digits is a compile error:
+/// ```compile_fail
+/// let pattern = zhol::byte_pattern!("8B 4 ??");
+/// ```
 #[macro_export]
 macro_rules! byte_pattern {
     ($pattern:expr) => {{
@@ -107,29 +563,70 @@ macro_rules! byte_pattern {
                 b'0'..=b'9' => c - b'0',
                 b'A'..=b'F' => c - b'A' + 10,
                 b'a'..=b'f' => c - b'a' + 10,
-                _ => panic!("Invalid hex character"),
+                _ => panic!("byte_pattern!: invalid hex character"),
             }
         }
 
-        const fn parse_byte_pair(first: u8, second: u8) -> Byte {
-            if first == b'?' && second == b'?' {
-                None
-            } else {
-                Some(hex_to_byte(first) << 4 | hex_to_byte(second))
+        const fn is_space(c: u8) -> bool {
+            c == b' ' || c == b'\t' || c == b'\n' || c == b'\r'
+        }
+
+        // Counts the whitespace-separated tokens, validating each is a wildcard or a two digit hex byte.
+        const fn count_tokens(s: &[u8]) -> usize {
+            let mut count = 0;
+            let mut i = 0;
+            while i < s.len() {
+                if is_space(s[i]) {
+                    i += 1;
+                    continue;
+                }
+
+                let start = i;
+                while i < s.len() && !is_space(s[i]) {
+                    i += 1;
+                }
+
+                let len = i - start;
+                if s[start] == b'?' {
+                    if len > 2 || (len == 2 && s[start + 1] != b'?') {
+                        panic!("byte_pattern!: wildcards must be \"?\" or \"??\"");
+                    }
+                } else if len != 2 {
+                    panic!("byte_pattern!: bytes must be two hex digits separated by whitespace");
+                }
+
+                count += 1;
+            }
+
+            if count == 0 {
+                panic!("byte_pattern!: pattern is empty");
             }
+
+            count
         }
 
         const PATTERN_STR: &[u8] = $pattern.as_bytes();
-        const LEN: usize = PATTERN_STR.len() / 3; // "XX " format
+        const LEN: usize = count_tokens(PATTERN_STR);
 
-        const PATTERN: [Byte; LEN] = {
+        const PATTERN: [$crate::memory::Byte; LEN] = {
             let mut arr = [None; LEN];
             let mut i = 0;
-            while i < LEN {
-                let first = PATTERN_STR[i * 3];
-                let second = PATTERN_STR[i * 3 + 1];
-                arr[i] = parse_byte_pair(first, second);
-                i += 1;
+            let mut n = 0;
+            while i < PATTERN_STR.len() {
+                if is_space(PATTERN_STR[i]) {
+                    i += 1;
+                    continue;
+                }
+
+                arr[n] = match PATTERN_STR[i] {
+                    b'?' => None,
+                    first => Some(hex_to_byte(first) << 4 | hex_to_byte(PATTERN_STR[i + 1])),
+                };
+
+                while i < PATTERN_STR.len() && !is_space(PATTERN_STR[i]) {
+                    i += 1;
+                }
+                n += 1;
             }
             arr
         };
@@ -137,3 +634,240 @@ macro_rules! byte_pattern {
         &PATTERN
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_pattern_parses_bytes_and_wildcards() {
+        let pattern = crate::byte_pattern!("8B 45 ?? 89 ? 08");
+
+        assert_eq!(pattern, &[Some(0x8B), Some(0x45), None, Some(0x89), None, Some(0x08)]);
+    }
+
+    #[test]
+    fn byte_pattern_tolerates_irregular_whitespace() {
+        let pattern = crate::byte_pattern!("  e8\t??  ff\n");
+
+        assert_eq!(pattern, &[Some(0xE8), None, Some(0xFF)]);
+    }
+
+    #[test]
+    fn byte_pattern_accepts_wildcards_only() {
+        assert_eq!(crate::byte_pattern!("?? ?"), &[None, None]);
+    }
+
+    fn scan_chunked(
+        bytes: &[u8],
+        pattern: &[Byte],
+        window_size: usize,
+        limit: Option<usize>,
+    ) -> Vec<(usize, Vec<u8>)> {
+        find_pattern_chunked(pattern, bytes.len(), window_size, limit, |offset, len| {
+            Ok(bytes[offset..offset + len].to_vec())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn find_pattern_chunked_finds_matches_across_window_boundaries() {
+        let mut bytes = vec![0x90; 0x20];
+        bytes[0x06..0x0A].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        bytes[0x0F..0x13].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        bytes[0x1C..0x20].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        let pattern = crate::byte_pattern!("DE ?? BE EF");
+
+        for window_size in [1, 3, 0x8, 0x10, 0x40] {
+            let offsets: Vec<usize> = scan_chunked(&bytes, pattern, window_size, None)
+                .into_iter()
+                .map(|(offset, _)| offset)
+                .collect();
+
+            assert_eq!(offsets, vec![0x06, 0x0F, 0x1C], "window size {window_size:#X}");
+        }
+    }
+
+    #[test]
+    fn find_pattern_chunked_stops_at_limit() {
+        let bytes = [0xCC, 0x90, 0xCC, 0x90, 0xCC];
+
+        let matches = scan_chunked(&bytes, crate::byte_pattern!("CC"), 0x2, Some(2));
+
+        assert_eq!(matches, vec![(0x0, vec![0xCC]), (0x2, vec![0xCC])]);
+    }
+
+    #[test]
+    fn find_pattern_chunked_rejects_empty_windows() {
+        let result = find_pattern_chunked(crate::byte_pattern!("CC"), 0x10, 0, None, |_, len| {
+            Ok(vec![0; len])
+        });
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn resolve_signature_in_image_follows_each_anchor() {
+        #[rustfmt::skip]
+        let image = [
+            0x90, 0x90,
+            0xE8, 0x10, 0x00, 0x00, 0x00, // call +0x10
+            0xA1, 0x78, 0x56, 0x34, 0x12, // mov eax, [0x12345678]
+        ];
+        let resolve = |pattern, anchor| resolve_signature_in_image(&image, 0x400000, pattern, anchor);
+
+        assert_eq!(resolve("E8 ?? ?? ?? ?? A1", SignatureAnchor::Offset(5)).unwrap(), 0x400007);
+        assert_eq!(resolve("E8 ?? ?? ?? ?? A1", SignatureAnchor::Offset(-2)).unwrap(), 0x400000);
+        assert_eq!(
+            resolve("E8", SignatureAnchor::Relative { disp_offset: 1, instr_len: 5 }).unwrap(),
+            0x400017
+        );
+        assert_eq!(resolve("A1", SignatureAnchor::Absolute32(1)).unwrap(), 0x12345678);
+        assert!(matches!(
+            resolve("CC", SignatureAnchor::Offset(0)),
+            Err(crate::MemOpError::PatternNotFound)
+        ));
+    }
+
+    fn section(name: &str, rva: usize, virtual_size: usize) -> SectionInfo {
+        SectionInfo {
+            name: name.to_string(),
+            virtual_address: rva,
+            virtual_size,
+            raw_size: virtual_size,
+            raw_offset: rva,
+            characteristics: 0,
+        }
+    }
+
+    #[test]
+    fn scan_module_buffer_only_scans_the_section() {
+        let sections = [section(".text", 0x1000, 0x100), section(".data", 0x2000, 0x100)];
+        let mut image = vec![0u8; 0x3000];
+        image[0x1010..0x1014].copy_from_slice(&[0x55, 0x8B, 0xEC, 0x90]);
+        // Straddles the end of .text, so it isn't part of it
+        image[0x10FE..0x1102].copy_from_slice(&[0x55, 0x8B, 0xEC, 0x90]);
+        // Decoy in .data
+        image[0x2010..0x2014].copy_from_slice(&[0x55, 0x8B, 0xEC, 0x90]);
+
+        let text = scan_module_buffer(&image, &sections, ".text", "55 8B EC ??").unwrap();
+        let data = scan_module_buffer(&image, &sections, ".data", "55 8B EC ??").unwrap();
+
+        assert_eq!(text, vec![0x1010]);
+        assert_eq!(data, vec![0x2010]);
+        assert!(scan_module_buffer(&image, &sections, ".rdata", "55").is_err());
+    }
+
+    #[test]
+    fn scan_module_buffer_clamps_sections_past_the_buffer() {
+        let sections = [section(".text", 0x1000, 0x1000)];
+        let mut image = vec![0u8; 0x1800];
+        image[0x17FE..].copy_from_slice(&[0xC3, 0xCC]);
+
+        assert_eq!(
+            scan_module_buffer(&image, &sections, ".text", "C3 CC").unwrap(),
+            vec![0x17FE]
+        );
+    }
+
+    #[test]
+    fn find_pattern_context_in_bytes_includes_surrounding_bytes() {
+        let bytes = [0x10, 0x11, 0x12, 0xAA, 0xBB, 0x13, 0x14, 0x15, 0x16];
+        let pattern = prepare_pattern("AA BB");
+
+        assert_eq!(
+            find_pattern_context_in_bytes(&bytes, &pattern, 2, 3),
+            vec![(3, vec![0x11, 0x12, 0xAA, 0xBB, 0x13, 0x14, 0x15])]
+        );
+        assert_eq!(
+            find_pattern_context_in_bytes(&bytes, &pattern, 0, 0),
+            vec![(3, vec![0xAA, 0xBB])]
+        );
+    }
+
+    #[test]
+    fn find_pattern_context_in_bytes_clamps_to_the_buffer() {
+        let bytes = [0xAA, 0x01, 0x02, 0x03, 0xAA];
+        let pattern = prepare_pattern("AA");
+
+        assert_eq!(
+            find_pattern_context_in_bytes(&bytes, &pattern, 2, 2),
+            vec![(0, vec![0xAA, 0x01, 0x02]), (4, vec![0x02, 0x03, 0xAA])]
+        );
+        assert_eq!(
+            find_pattern_context_in_bytes(&bytes, &pattern, usize::MAX, usize::MAX),
+            vec![(0, bytes.to_vec()), (4, bytes.to_vec())]
+        );
+    }
+
+    #[test]
+    fn group_by_module_splits_matches_by_image() {
+        let modules = [
+            ("game.exe".to_string(), 0x400000..0x500000),
+            ("unused.dll".to_string(), 0x600000..0x700000),
+            ("engine.dll".to_string(), 0x10000000..0x10100000),
+        ];
+        let addrs = [0x10000010, 0x401000, 0x2000, 0x4FFFFF, 0x500000];
+
+        assert_eq!(
+            group_by_module(&modules, addrs),
+            vec![
+                ("game.exe".to_string(), vec![0x401000, 0x4FFFFF]),
+                ("engine.dll".to_string(), vec![0x10000010]),
+                (UNNAMED_REGION_GROUP.to_string(), vec![0x2000, 0x500000]),
+            ]
+        );
+    }
+
+    #[test]
+    fn group_by_module_without_unnamed_matches() {
+        let modules = [("game.exe".to_string(), 0x400000..0x500000)];
+
+        assert_eq!(
+            group_by_module(&modules, [0x400000]),
+            vec![("game.exe".to_string(), vec![0x400000])]
+        );
+        assert!(group_by_module(&modules, []).is_empty());
+    }
+
+    #[test]
+    fn find_pattern_offsets_matches_find_pattern_in_bytes() {
+        #[rustfmt::skip]
+        let bytes = vec![
+            0x55, 0x8B, 0xEC, 0x55, 0x8B, 0x55, 0x8B, 0xEC, 0x8B, 0xEC, 0x55, 0x8B,
+        ];
+
+        let patterns = ["55 8B", "55 ?? EC", "8B EC", "??", "EC 55 8B EC", "55 8B EC 8B EC 55 8B 00"];
+
+        for pattern in patterns {
+            let pattern = prepare_pattern(pattern);
+            let offsets: Vec<usize> = find_pattern_in_bytes(bytes.clone(), &pattern[..], None)
+                .unwrap()
+                .into_iter()
+                .map(|(offset, _)| offset)
+                .collect();
+
+            assert_eq!(find_pattern_offsets(&bytes, &pattern), offsets, "{pattern:?}");
+        }
+    }
+
+    #[test]
+    fn pattern_forms_are_equal_and_match_alike() {
+        let bytes = [0x90, 0x8B, 0x45, 0x08, 0x85, 0xC0, 0x8B, 0x4D, 0x08, 0x85, 0xC0];
+        let optional: &[Byte] = &[Some(0x8B), None, Some(0x08), Some(0x85)];
+
+        let ida = Pattern::from("8B ?? 08 85");
+        let from_bytes = Pattern::from(optional);
+        let masked = Pattern::from((&[0x8B, 0x00, 0x08, 0x85][..], "x?xx"));
+        // Bytes past the end of the mask must match
+        let short_mask = Pattern::from((&[0x8B, 0x00, 0x08, 0x85][..], "x?"));
+
+        assert_eq!(ida, from_bytes);
+        assert_eq!(ida, masked);
+        assert_eq!(ida, short_mask);
+
+        for pattern in [ida, from_bytes, masked, short_mask] {
+            assert_eq!(find_pattern_offsets(&bytes, pattern.bytes()), vec![1, 6]);
+        }
+    }
+}
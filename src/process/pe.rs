@@ -0,0 +1,551 @@
+use crate::{memop_err, MemOpResult};
+
+/// Index of the export table in the optional header's data directories.
+pub const IMAGE_DIRECTORY_ENTRY_EXPORT: usize = 0;
+
+/// Index of the import table in the optional header's data directories.
+pub const IMAGE_DIRECTORY_ENTRY_IMPORT: usize = 1;
+
+/// Amount of bytes read from a module base when parsing its headers.
+pub const HEADERS_SIZE: usize = 0x1000;
+
+/// Reads a little-endian u16 at a given offset, erroring if out of bounds.
+pub fn read_u16(bytes: &[u8], offset: usize) -> MemOpResult<u16> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or(memop_err!("PE read of u16 at offset \"{offset:#X}\" out of bounds."))
+}
+
+/// Reads a little-endian u32 at a given offset, erroring if out of bounds.
+pub fn read_u32(bytes: &[u8], offset: usize) -> MemOpResult<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or(memop_err!("PE read of u32 at offset \"{offset:#X}\" out of bounds."))
+}
+
+/// Reads a little-endian u64 at a given offset, erroring if out of bounds.
+pub fn read_u64(bytes: &[u8], offset: usize) -> MemOpResult<u64> {
+    bytes
+        .get(offset..offset + 8)
+        .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        .ok_or(memop_err!("PE read of u64 at offset \"{offset:#X}\" out of bounds."))
+}
+
+/// Reads a null-terminated ASCII string at a given offset, erroring if out of bounds or unterminated.
+pub fn read_c_str(bytes: &[u8], offset: usize) -> MemOpResult<String> {
+    let tail = bytes
+        .get(offset..)
+        .ok_or(memop_err!("PE read of string at offset \"{offset:#X}\" out of bounds."))?;
+
+    let end = tail
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or(memop_err!("PE string at offset \"{offset:#X}\" is not terminated."))?;
+
+    Ok(String::from_utf8_lossy(&tail[..end]).into_owned())
+}
+
+/// Returns the offset of the NT headers (`PE\0\0`) within a module's headers.
+///
+/// # Arguments
+/// * `headers`: Bytes read from the module base
+/// # Returns
+/// * `MemOpResult<usize>`: Offset of the NT headers, validated against both the DOS and PE signatures
+pub fn nt_headers_offset(headers: &[u8]) -> MemOpResult<usize> {
+    if read_u16(headers, 0)? != 0x5A4D {
+        return Err(memop_err!("Module headers do not start with a DOS signature."));
+    }
+
+    let e_lfanew = read_u32(headers, 0x3C)? as usize;
+    if read_u32(headers, e_lfanew)? != 0x4550 {
+        return Err(memop_err!("Module headers are missing the PE signature."));
+    }
+
+    Ok(e_lfanew)
+}
+
+/// Returns whether the module headers describe a PE32+ (64-bit) image.
+pub fn is_pe32_plus(headers: &[u8]) -> MemOpResult<bool> {
+    let optional = nt_headers_offset(headers)? + 0x18;
+
+    match read_u16(headers, optional)? {
+        0x10B => Ok(false),
+        0x20B => Ok(true),
+        magic => Err(memop_err!("Unknown optional header magic \"{magic:#X}\".")),
+    }
+}
+
+/// Returns the (RVA, size) of a data directory entry.
+///
+/// # Arguments
+/// * `headers`: Bytes read from the module base
+/// * `index`: Data directory index, e.g. `IMAGE_DIRECTORY_ENTRY_EXPORT`
+/// # Returns
+/// * `MemOpResult<(usize, usize)>`: RVA and size of the directory
+pub fn data_directory(headers: &[u8], index: usize) -> MemOpResult<(usize, usize)> {
+    let optional = nt_headers_offset(headers)? + 0x18;
+    let (count_offset, directories) = match is_pe32_plus(headers)? {
+        true => (optional + 108, optional + 112),
+        false => (optional + 92, optional + 96),
+    };
+
+    let count = read_u32(headers, count_offset)? as usize;
+    if index >= count {
+        return Err(memop_err!(
+            "Data directory \"{index}\" not present, module only has \"{count}\"."
+        ));
+    }
+
+    let entry = directories + index * 8;
+    Ok((
+        read_u32(headers, entry)? as usize,
+        read_u32(headers, entry + 4)? as usize,
+    ))
+}
+
+/// A section header of a PE image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SectionInfo {
+    /// Section name, e.g. `.text`, with trailing nulls stripped
+    pub name: String,
+    /// RVA the section is mapped at
+    pub virtual_address: usize,
+    /// Size of the section once mapped
+    pub virtual_size: usize,
+    /// Size of the section's data in the file
+    pub raw_size: usize,
+    /// File offset of the section's data
+    pub raw_offset: usize,
+    /// `IMAGE_SCN_*` flags
+    pub characteristics: u32,
+}
+
+impl SectionInfo {
+    /// Returns the RVA range the section occupies once mapped.
+    ///
+    /// Some linkers leave the virtual size zeroed, in which case the raw size is used.
+    pub fn rva_range(&self) -> std::ops::Range<usize> {
+        let size = match self.virtual_size {
+            0 => self.raw_size,
+            size => size,
+        };
+
+        self.virtual_address..self.virtual_address + size
+    }
+}
+
+/// Parses the section table following the optional header.
+///
+/// # Arguments
+/// * `headers`: Bytes read from the module base
+/// # Returns
+/// * `MemOpResult<Vec<SectionInfo>>`: Every section header, in table order
+pub fn sections(headers: &[u8]) -> MemOpResult<Vec<SectionInfo>> {
+    let nt = nt_headers_offset(headers)?;
+    let count = read_u16(headers, nt + 0x6)? as usize;
+    let optional_size = read_u16(headers, nt + 0x14)? as usize;
+    let table = nt + 0x18 + optional_size;
+
+    (0..count)
+        .map(|i| {
+            let entry = table + i * 40;
+            let name = headers
+                .get(entry..entry + 8)
+                .ok_or(memop_err!("Section header \"{i}\" out of bounds."))?;
+
+            Ok(SectionInfo {
+                name: String::from_utf8_lossy(name)
+                    .trim_end_matches('\0')
+                    .to_string(),
+                virtual_size: read_u32(headers, entry + 8)? as usize,
+                virtual_address: read_u32(headers, entry + 12)? as usize,
+                raw_size: read_u32(headers, entry + 16)? as usize,
+                raw_offset: read_u32(headers, entry + 20)? as usize,
+                characteristics: read_u32(headers, entry + 36)?,
+            })
+        })
+        .collect()
+}
+
+/// A parsed PE export directory, holding the raw directory bytes it was parsed from.
+///
+/// All lookups are resolved within `bytes`, so the whole export data directory should be read.
+#[derive(Clone, Debug)]
+pub struct ExportDirectory {
+    /// RVA the directory bytes were read from
+    pub rva: usize,
+    /// Raw bytes of the export data directory
+    pub bytes: Vec<u8>,
+    pub ordinal_base: u32,
+    pub number_of_functions: usize,
+    pub number_of_names: usize,
+    functions_rva: usize,
+    names_rva: usize,
+    ordinals_rva: usize,
+}
+
+impl ExportDirectory {
+    /// Parses an export directory from the bytes of its data directory.
+    ///
+    /// # Arguments
+    /// * `rva`: RVA of the export data directory
+    /// * `bytes`: Bytes of the export data directory
+    pub fn parse(rva: usize, bytes: Vec<u8>) -> MemOpResult<Self> {
+        Ok(ExportDirectory {
+            rva,
+            ordinal_base: read_u32(&bytes, 0x10)?,
+            number_of_functions: read_u32(&bytes, 0x14)? as usize,
+            number_of_names: read_u32(&bytes, 0x18)? as usize,
+            functions_rva: read_u32(&bytes, 0x1C)? as usize,
+            names_rva: read_u32(&bytes, 0x20)? as usize,
+            ordinals_rva: read_u32(&bytes, 0x24)? as usize,
+            bytes,
+        })
+    }
+
+    /// Converts an RVA into an offset within `bytes`.
+    fn offset(&self, rva: usize) -> MemOpResult<usize> {
+        match rva.checked_sub(self.rva) {
+            Some(o) if o < self.bytes.len() => Ok(o),
+            _ => Err(memop_err!(
+                "RVA \"{rva:#X}\" lies outside of the export directory."
+            )),
+        }
+    }
+
+    /// Returns the name of every export that has one.
+    pub fn names(&self) -> MemOpResult<Vec<String>> {
+        (0..self.number_of_names)
+            .map(|i| {
+                let name_rva = read_u32(&self.bytes, self.offset(self.names_rva + i * 4)?)?;
+                read_c_str(&self.bytes, self.offset(name_rva as usize)?)
+            })
+            .collect()
+    }
+
+    /// Returns the RVA of the function exported under a given name.
+    pub fn rva_by_name(&self, name: &str) -> MemOpResult<usize> {
+        for i in 0..self.number_of_names {
+            let name_rva = read_u32(&self.bytes, self.offset(self.names_rva + i * 4)?)?;
+            if read_c_str(&self.bytes, self.offset(name_rva as usize)?)? != name {
+                continue;
+            }
+
+            let index = read_u16(&self.bytes, self.offset(self.ordinals_rva + i * 2)?)?;
+            return self.function_rva(index as usize);
+        }
+
+        Err(memop_err!("No export named \"{name}\"."))
+    }
+
+    /// Returns the RVA of the function exported under a given ordinal.
+    ///
+    /// Ordinals are biased by `ordinal_base`, so the export address table index is `ordinal - ordinal_base`.
+    pub fn rva_by_ordinal(&self, ordinal: u32) -> MemOpResult<usize> {
+        let index = ordinal.checked_sub(self.ordinal_base).ok_or(memop_err!(
+            "Ordinal \"{ordinal}\" is below the ordinal base \"{}\".",
+            self.ordinal_base
+        ))?;
+
+        self.function_rva(index as usize)
+    }
+
+    /// Returns the RVA of the function at a given index of the export address table.
+    fn function_rva(&self, index: usize) -> MemOpResult<usize> {
+        if index >= self.number_of_functions {
+            return Err(memop_err!(
+                "Export index \"{index}\" out of range of \"{}\" functions.",
+                self.number_of_functions
+            ));
+        }
+
+        let rva = read_u32(&self.bytes, self.offset(self.functions_rva + index * 4)?)? as usize;
+        if rva == 0 {
+            return Err(memop_err!("Export index \"{index}\" has no function."));
+        }
+
+        // Forwarded exports point at a "dll.function" string inside the directory rather than code.
+        if rva >= self.rva && rva < self.rva + self.bytes.len() {
+            return Err(memop_err!(
+                "Export index \"{index}\" is forwarded to \"{}\".",
+                read_c_str(&self.bytes, rva - self.rva)?
+            ));
+        }
+
+        Ok(rva)
+    }
+}
+
+/// Parses the import directory of a module image into each imported DLL and its imported functions.
+///
+/// Functions are looked up through the import name table, as the import address table of a loaded
+/// image already holds resolved addresses. Functions imported by ordinal are named `#<ordinal>`,
+/// and DLLs without an import name table (some bound images) are listed without functions.
+///
+/// # Arguments
+/// * `image`: The whole module image indexed by RVA, e.g. from `read_module_image()`
+/// # Returns
+/// * `MemOpResult<Vec<(String, Vec<String>)>>`: DLL names in import order, with their function names
+pub fn imports(image: &[u8]) -> MemOpResult<Vec<(String, Vec<String>)>> {
+    let (rva, size) = data_directory(image, IMAGE_DIRECTORY_ENTRY_IMPORT)?;
+    if rva == 0 || size == 0 {
+        return Ok(Vec::new());
+    }
+
+    let pe32_plus = is_pe32_plus(image)?;
+    let mut result = Vec::new();
+
+    // Descriptors are 20 bytes, terminated by an all-zero one.
+    for descriptor in (rva..).step_by(20) {
+        let name_table = read_u32(image, descriptor)? as usize;
+        let name_rva = read_u32(image, descriptor + 12)? as usize;
+        if name_rva == 0 && read_u32(image, descriptor + 16)? == 0 {
+            break;
+        }
+
+        let dll = read_c_str(image, name_rva)?;
+        let mut functions = Vec::new();
+
+        if name_table != 0 {
+            for i in 0.. {
+                let (thunk, by_ordinal) = match pe32_plus {
+                    true => {
+                        let thunk = read_u64(image, name_table + i * 8)?;
+                        (thunk, thunk & (1 << 63) != 0)
+                    }
+                    false => {
+                        let thunk = read_u32(image, name_table + i * 4)? as u64;
+                        (thunk, thunk & (1 << 31) != 0)
+                    }
+                };
+
+                if thunk == 0 {
+                    break;
+                }
+
+                functions.push(match by_ordinal {
+                    true => format!("#{}", thunk & 0xFFFF),
+                    // IMAGE_IMPORT_BY_NAME, a u16 hint followed by the name
+                    false => read_c_str(image, (thunk & 0x7FFF_FFFF) as usize + 2)?,
+                });
+            }
+        }
+
+        result.push((dll, functions));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Offset of the NT headers in `headers()`.
+    const NT: usize = 0x80;
+
+    /// Builds minimal module headers with the given sections, as (name, RVA, virtual size, raw size).
+    fn headers(pe32_plus: bool, sections: &[(&str, u32, u32, u32)]) -> Vec<u8> {
+        let mut headers = vec![0u8; HEADERS_SIZE];
+        let (optional_size, magic): (u16, u16) = match pe32_plus {
+            true => (0xF0, 0x20B),
+            false => (0xE0, 0x10B),
+        };
+
+        headers[0x0..0x2].copy_from_slice(b"MZ");
+        headers[0x3C..0x40].copy_from_slice(&(NT as u32).to_le_bytes());
+        headers[NT..NT + 0x4].copy_from_slice(b"PE\0\0");
+        headers[NT + 0x6..NT + 0x8].copy_from_slice(&(sections.len() as u16).to_le_bytes());
+        headers[NT + 0x14..NT + 0x16].copy_from_slice(&optional_size.to_le_bytes());
+        headers[NT + 0x18..NT + 0x1A].copy_from_slice(&magic.to_le_bytes());
+
+        let table = NT + 0x18 + optional_size as usize;
+        for (i, &(name, rva, virtual_size, raw_size)) in sections.iter().enumerate() {
+            let entry = table + i * 40;
+            headers[entry..entry + name.len()].copy_from_slice(name.as_bytes());
+            headers[entry + 8..entry + 12].copy_from_slice(&virtual_size.to_le_bytes());
+            headers[entry + 12..entry + 16].copy_from_slice(&rva.to_le_bytes());
+            headers[entry + 16..entry + 20].copy_from_slice(&raw_size.to_le_bytes());
+            headers[entry + 20..entry + 24].copy_from_slice(&0x400u32.to_le_bytes());
+            headers[entry + 36..entry + 40].copy_from_slice(&0x6000_0020u32.to_le_bytes());
+        }
+
+        headers
+    }
+
+    #[test]
+    fn nt_headers_offset_checks_both_signatures() {
+        let mut bytes = headers(false, &[]);
+        assert_eq!(nt_headers_offset(&bytes).unwrap(), NT);
+        assert!(!is_pe32_plus(&bytes).unwrap());
+        assert!(is_pe32_plus(&headers(true, &[])).unwrap());
+
+        bytes[NT] = 0;
+        assert!(nt_headers_offset(&bytes).is_err());
+        bytes[0] = 0;
+        assert!(nt_headers_offset(&bytes).is_err());
+    }
+
+    #[test]
+    fn sections_parses_the_section_table() {
+        for pe32_plus in [false, true] {
+            let bytes = headers(
+                pe32_plus,
+                &[(".text", 0x1000, 0x234, 0x400), (".data", 0x2000, 0, 0x200)],
+            );
+
+            let sections = sections(&bytes).unwrap();
+
+            assert_eq!(sections.len(), 2);
+            assert_eq!(
+                sections[0],
+                SectionInfo {
+                    name: ".text".to_string(),
+                    virtual_address: 0x1000,
+                    virtual_size: 0x234,
+                    raw_size: 0x400,
+                    raw_offset: 0x400,
+                    characteristics: 0x6000_0020,
+                }
+            );
+            assert_eq!(sections[0].rva_range(), 0x1000..0x1234);
+            // A zeroed virtual size falls back to the raw size.
+            assert_eq!(sections[1].name, ".data");
+            assert_eq!(sections[1].rva_range(), 0x2000..0x2200);
+        }
+    }
+
+    #[test]
+    fn sections_rejects_tables_past_the_headers() {
+        let mut bytes = headers(false, &[(".text", 0x1000, 0x234, 0x400)]);
+        bytes[NT + 0x6..NT + 0x8].copy_from_slice(&0x100u16.to_le_bytes());
+
+        assert!(sections(&bytes).is_err());
+    }
+
+    /// Points a data directory of `headers()` at the given RVA and size.
+    fn set_directory(image: &mut [u8], pe32_plus: bool, index: usize, rva: u32, size: u32) {
+        let optional = NT + 0x18;
+        let (count_offset, directories) = match pe32_plus {
+            true => (optional + 108, optional + 112),
+            false => (optional + 92, optional + 96),
+        };
+
+        image[count_offset..count_offset + 4].copy_from_slice(&16u32.to_le_bytes());
+        let entry = directories + index * 8;
+        image[entry..entry + 4].copy_from_slice(&rva.to_le_bytes());
+        image[entry + 4..entry + 8].copy_from_slice(&size.to_le_bytes());
+    }
+
+    fn put_u32(image: &mut [u8], offset: usize, value: u32) {
+        image[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_str(image: &mut [u8], offset: usize, value: &str) {
+        image[offset..offset + value.len()].copy_from_slice(value.as_bytes());
+    }
+
+    /// Builds an image importing `ReadFile` and ordinal 16 from KERNEL32.dll,
+    /// and a DLL without an import name table.
+    fn import_image(pe32_plus: bool) -> Vec<u8> {
+        let mut image = headers(pe32_plus, &[]);
+        image.resize(0x3000, 0);
+        set_directory(&mut image, pe32_plus, IMAGE_DIRECTORY_ENTRY_IMPORT, 0x2000, 0x3C);
+
+        // Descriptors: OriginalFirstThunk, TimeDateStamp, ForwarderChain, Name, FirstThunk
+        put_u32(&mut image, 0x2000, 0x2100);
+        put_u32(&mut image, 0x200C, 0x2200);
+        put_u32(&mut image, 0x2010, 0x2300);
+        put_u32(&mut image, 0x2020, 0x2210);
+        put_u32(&mut image, 0x2024, 0x2310);
+
+        match pe32_plus {
+            true => {
+                image[0x2100..0x2108].copy_from_slice(&0x2400u64.to_le_bytes());
+                image[0x2108..0x2110].copy_from_slice(&(1u64 << 63 | 16).to_le_bytes());
+            }
+            false => {
+                put_u32(&mut image, 0x2100, 0x2400);
+                put_u32(&mut image, 0x2104, 1 << 31 | 16);
+            }
+        }
+
+        put_str(&mut image, 0x2200, "KERNEL32.dll");
+        put_str(&mut image, 0x2210, "bound.dll");
+        // IMAGE_IMPORT_BY_NAME, the hint is left zeroed
+        put_str(&mut image, 0x2402, "ReadFile");
+
+        image
+    }
+
+    #[test]
+    fn imports_reads_names_and_ordinals() {
+        for pe32_plus in [false, true] {
+            let imports = imports(&import_image(pe32_plus)).unwrap();
+
+            assert_eq!(
+                imports,
+                vec![
+                    (
+                        "KERNEL32.dll".to_string(),
+                        vec!["ReadFile".to_string(), "#16".to_string()]
+                    ),
+                    ("bound.dll".to_string(), Vec::new()),
+                ],
+                "pe32_plus: {pe32_plus}"
+            );
+        }
+    }
+
+    #[test]
+    fn imports_of_image_without_import_directory() {
+        let mut image = headers(false, &[]);
+        set_directory(&mut image, false, IMAGE_DIRECTORY_ENTRY_EXPORT, 0x2000, 0x40);
+
+        assert!(imports(&image).unwrap().is_empty());
+    }
+
+    /// Builds an export directory at RVA 0x3000 with an ordinal base of 5, exporting `Foo`
+    /// (ordinal 5), a forward to `NTDLL.RtlFoo` (ordinal 6) and an empty slot (ordinal 7).
+    fn export_directory() -> ExportDirectory {
+        let mut bytes = vec![0u8; 0x100];
+        put_u32(&mut bytes, 0x10, 5);
+        put_u32(&mut bytes, 0x14, 3);
+        put_u32(&mut bytes, 0x18, 1);
+        put_u32(&mut bytes, 0x1C, 0x3040);
+        put_u32(&mut bytes, 0x20, 0x3050);
+        put_u32(&mut bytes, 0x24, 0x3058);
+
+        put_u32(&mut bytes, 0x40, 0x1000);
+        put_u32(&mut bytes, 0x44, 0x3060);
+        put_u32(&mut bytes, 0x50, 0x3080);
+        put_str(&mut bytes, 0x60, "NTDLL.RtlFoo");
+        put_str(&mut bytes, 0x80, "Foo");
+
+        ExportDirectory::parse(0x3000, bytes).unwrap()
+    }
+
+    #[test]
+    fn rva_by_ordinal_subtracts_the_ordinal_base() {
+        let exports = export_directory();
+
+        assert_eq!(exports.rva_by_ordinal(5).unwrap(), 0x1000);
+        assert_eq!(exports.rva_by_name("Foo").unwrap(), 0x1000);
+        assert_eq!(exports.names().unwrap(), vec!["Foo".to_string()]);
+    }
+
+    #[test]
+    fn rva_by_ordinal_rejects_forwarded_and_missing_exports() {
+        let exports = export_directory();
+
+        let forwarded = exports.rva_by_ordinal(6).unwrap_err();
+        assert!(forwarded.to_string().contains("NTDLL.RtlFoo"), "{forwarded}");
+
+        assert!(exports.rva_by_ordinal(4).is_err());
+        assert!(exports.rva_by_ordinal(7).is_err());
+        assert!(exports.rva_by_ordinal(8).is_err());
+        assert!(exports.rva_by_name("Bar").is_err());
+    }
+}
@@ -0,0 +1,408 @@
+use crate::memory::utils::{allocate_memory, change_memory_protection, get_last_error};
+use crate::memory::read::read_bytes;
+use crate::memory::write::write_bytes;
+use crate::process::SafeHandle;
+use crate::{with_handle, MemOpError, MemOpResult};
+
+use anyhow::anyhow;
+use core::ffi::c_void;
+use std::time::Duration;
+
+use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0, WAIT_TIMEOUT};
+use windows::Win32::System::Memory::PAGE_EXECUTE_READ;
+use windows::Win32::System::Threading::{
+    CreateRemoteThread, GetExitCodeThread, WaitForSingleObject, INFINITE,
+};
+
+/// Waits for a thread to exit and returns its exit code.
+///
+/// # Arguments
+/// * `thread` - Handle to the thread, with SYNCHRONIZE and THREAD_QUERY_INFORMATION access
+/// * `timeout` - Optional timeout for the wait, `None` waits indefinitely
+///
+/// # Returns
+/// Returns the exit code of the thread, or `TimeoutReached` if it is still running
+pub fn wait_for_thread(thread: HANDLE, timeout: Option<Duration>) -> MemOpResult<u32> {
+    let millis = match timeout {
+        Some(t) => t.as_millis().min((INFINITE - 1) as u128) as u32,
+        None => INFINITE,
+    };
+
+    match unsafe { WaitForSingleObject(thread, millis) } {
+        WAIT_OBJECT_0 => {
+            let mut exit_code = 0u32;
+            unsafe { GetExitCodeThread(thread, &mut exit_code)? };
+            Ok(exit_code)
+        }
+        WAIT_TIMEOUT => Err(MemOpError::TimeoutReached((
+            timeout,
+            Some(anyhow!("Waiting for remote thread to exit")),
+        ))),
+        _ => Err(get_last_error()),
+    }
+}
+
+/// Runs a stub of machine code in the target process on a new remote thread.
+///
+/// The code is written to a freshly allocated region which is made executable (and no longer writable),
+/// then run through `CreateRemoteThread`. The region is freed once the thread exits. If the thread does not
+/// exit within the timeout the region is deliberately leaked, as the thread may still be executing it.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `code` - The machine code to run, following the `LPTHREAD_START_ROUTINE` convention of the target
+/// * `timeout` - Optional timeout for the handle acquisitions and the thread to exit
+///
+/// # Returns
+/// Returns the exit code of the remote thread, i.e. the value the stub returned
+///
+/// # Example
+/// ```rust,norun
+/// // mov eax, 0x2A; ret 4
+/// let exit_code = execute_shellcode(&handle, &[0xB8, 0x2A, 0x00, 0x00, 0x00, 0xC2, 0x04, 0x00], None)?;
+/// assert_eq!(exit_code, 0x2A);
+/// ```
+pub fn execute_shellcode(
+    handle: &SafeHandle,
+    code: &[u8],
+    timeout: Option<Duration>,
+) -> MemOpResult<u32> {
+    let region = allocate_memory(handle, code.len())?;
+    write_bytes(handle, region.addr, code, timeout)?;
+    change_memory_protection(handle, region.addr, region.size, timeout, PAGE_EXECUTE_READ)?;
+
+    let thread = with_handle!(handle, timeout, |guard| -> HANDLE, {
+        unsafe {
+            let start: unsafe extern "system" fn(*mut c_void) -> u32 =
+                std::mem::transmute(region.addr);
+
+            Ok(CreateRemoteThread(*guard, None, 0, Some(start), None, 0, None)?)
+        }
+    })?;
+
+    let exit_code = wait_for_thread(thread, timeout);
+    unsafe { _ = CloseHandle(thread) };
+
+    if exit_code.as_ref().is_err_and(|e| e.is_timeout()) {
+        std::mem::forget(region);
+    }
+
+    exit_code
+}
+
+/// Calling convention used by `call_remote()` to pass arguments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallingConvention {
+    /// x86, arguments pushed right-to-left and cleaned up by the caller
+    Cdecl,
+    /// x86, arguments pushed right-to-left and cleaned up by the callee
+    Stdcall,
+    /// x86, first two arguments in ecx/edx, the rest pushed right-to-left
+    Fastcall,
+    /// x64, first four arguments in rcx/rdx/r8/r9, the rest on the stack above the shadow space
+    Win64,
+}
+
+/// Builds a thread-start stub calling `func_addr` with `args`, storing the return register at `result_slot`.
+///
+/// # Arguments
+/// * `func_addr` - Address of the function to call
+/// * `args` - Arguments passed to the function
+/// * `convention` - Calling convention of the function, which also determines the stub's architecture
+/// * `result_slot` - Address of an 8 byte slot the return value is stored to
+///
+/// # Returns
+/// Returns the stub bytes, errors if an x86 call is given an address or argument wider than 32 bits
+pub fn build_call_stub(
+    func_addr: usize,
+    args: &[usize],
+    convention: CallingConvention,
+    result_slot: usize,
+) -> MemOpResult<Vec<u8>> {
+    let mut stub: Vec<u8> = Vec::new();
+
+    if convention == CallingConvention::Win64 {
+        let stack_args = args.len().saturating_sub(4);
+        // Shadow space plus stack args, keeping rsp 16 byte aligned at the call (it's 8 off on entry).
+        let frame = (0x20 + stack_args * 8).next_multiple_of(0x10) + 0x8;
+
+        stub.extend_from_slice(&[0x48, 0x81, 0xEC]); // sub rsp, imm32
+        stub.extend_from_slice(&(frame as u32).to_le_bytes());
+
+        for (i, arg) in args.iter().enumerate().skip(4) {
+            stub.extend_from_slice(&[0x48, 0xB8]); // mov rax, imm64
+            stub.extend_from_slice(&(*arg as u64).to_le_bytes());
+            stub.extend_from_slice(&[0x48, 0x89, 0x84, 0x24]); // mov [rsp + disp32], rax
+            stub.extend_from_slice(&((0x20 + (i - 4) * 8) as u32).to_le_bytes());
+        }
+
+        const REGISTER_MOVS: [[u8; 2]; 4] = [
+            [0x48, 0xB9], // mov rcx, imm64
+            [0x48, 0xBA], // mov rdx, imm64
+            [0x49, 0xB8], // mov r8, imm64
+            [0x49, 0xB9], // mov r9, imm64
+        ];
+        for (mov, arg) in REGISTER_MOVS.iter().zip(args.iter()) {
+            stub.extend_from_slice(mov);
+            stub.extend_from_slice(&(*arg as u64).to_le_bytes());
+        }
+
+        stub.extend_from_slice(&[0x48, 0xB8]); // mov rax, imm64
+        stub.extend_from_slice(&(func_addr as u64).to_le_bytes());
+        stub.extend_from_slice(&[0xFF, 0xD0]); // call rax
+        stub.extend_from_slice(&[0x48, 0xA3]); // mov [moffs64], rax
+        stub.extend_from_slice(&(result_slot as u64).to_le_bytes());
+        stub.extend_from_slice(&[0x48, 0x81, 0xC4]); // add rsp, imm32
+        stub.extend_from_slice(&(frame as u32).to_le_bytes());
+        stub.extend_from_slice(&[0x31, 0xC0, 0xC3]); // xor eax, eax; ret
+
+        return Ok(stub);
+    }
+
+    let to_u32 = |value: usize| -> MemOpResult<u32> {
+        u32::try_from(value).map_err(|_| {
+            crate::memop_err!("Value \"{value:#X}\" does not fit in a 32-bit {convention:?} call.")
+        })
+    };
+
+    let (register_args, stack_args) = match convention {
+        CallingConvention::Fastcall => args.split_at(args.len().min(2)),
+        _ => args.split_at(0),
+    };
+
+    for arg in stack_args.iter().rev() {
+        stub.push(0x68); // push imm32
+        stub.extend_from_slice(&to_u32(*arg)?.to_le_bytes());
+    }
+
+    for (mov, arg) in [0xB9u8, 0xBA].iter().zip(register_args.iter()) {
+        stub.push(*mov); // mov ecx/edx, imm32
+        stub.extend_from_slice(&to_u32(*arg)?.to_le_bytes());
+    }
+
+    stub.push(0xB8); // mov eax, imm32
+    stub.extend_from_slice(&to_u32(func_addr)?.to_le_bytes());
+    stub.extend_from_slice(&[0xFF, 0xD0]); // call eax
+
+    if convention == CallingConvention::Cdecl && !stack_args.is_empty() {
+        stub.extend_from_slice(&[0x81, 0xC4]); // add esp, imm32
+        stub.extend_from_slice(&((stack_args.len() * 4) as u32).to_le_bytes());
+    }
+
+    stub.push(0xA3); // mov [moffs32], eax
+    stub.extend_from_slice(&to_u32(result_slot)?.to_le_bytes());
+    stub.extend_from_slice(&[0x31, 0xC0, 0xC2, 0x04, 0x00]); // xor eax, eax; ret 4
+
+    Ok(stub)
+}
+
+/// Calls a function in the target process and returns its result.
+///
+/// A stub passing `args` per `convention` is run through `execute_shellcode()`, storing the return
+/// register (eax/rax) in a separately allocated result slot which is read back once the call returns.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `func_addr` - Address of the function to call
+/// * `args` - Arguments passed to the function
+/// * `convention` - Calling convention of the function. `Win64` targets x64 processes, the rest x86
+/// * `timeout` - Optional timeout for the handle acquisitions and the call to return
+///
+/// # Returns
+/// Returns the value of the return register after the call
+///
+/// # Example
+/// ```rust,norun
+/// let result = call_remote(&handle, func_addr, &[1, 2], CallingConvention::Stdcall, Some(Duration::from_secs(1)))?;
+/// ```
+pub fn call_remote(
+    handle: &SafeHandle,
+    func_addr: usize,
+    args: &[usize],
+    convention: CallingConvention,
+    timeout: Option<Duration>,
+) -> MemOpResult<usize> {
+    let result_slot = allocate_memory(handle, 0x8)?;
+    let stub = build_call_stub(func_addr, args, convention, result_slot.addr)?;
+
+    if let Err(e) = execute_shellcode(handle, &stub, timeout) {
+        if e.is_timeout() {
+            // The call may still return and store its result later.
+            std::mem::forget(result_slot);
+        }
+        return Err(e);
+    }
+
+    let raw = read_bytes(handle, result_slot.addr, 0x8, timeout)?;
+    let mut result = [0u8; 8];
+    result[..raw.len()].copy_from_slice(&raw);
+
+    Ok(u64::from_le_bytes(result) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::module::{get_export_address, module_by_name};
+    use crate::process::open_process;
+
+    #[test]
+    fn build_call_stub_cdecl_bytes() {
+        let stub = build_call_stub(0x401000, &[1, 2], CallingConvention::Cdecl, 0x500000).unwrap();
+
+        #[rustfmt::skip]
+        let expected = [
+            0x68, 0x02, 0x00, 0x00, 0x00, // push 2
+            0x68, 0x01, 0x00, 0x00, 0x00, // push 1
+            0xB8, 0x00, 0x10, 0x40, 0x00, // mov eax, 0x401000
+            0xFF, 0xD0, // call eax
+            0x81, 0xC4, 0x08, 0x00, 0x00, 0x00, // add esp, 8
+            0xA3, 0x00, 0x00, 0x50, 0x00, // mov [0x500000], eax
+            0x31, 0xC0, 0xC2, 0x04, 0x00, // xor eax, eax; ret 4
+        ];
+        assert_eq!(stub, expected);
+    }
+
+    #[test]
+    fn build_call_stub_fastcall_without_stack_args() {
+        let stub = build_call_stub(0x401000, &[1], CallingConvention::Fastcall, 0x500000).unwrap();
+
+        #[rustfmt::skip]
+        let expected = [
+            0xB9, 0x01, 0x00, 0x00, 0x00, // mov ecx, 1
+            0xB8, 0x00, 0x10, 0x40, 0x00, // mov eax, 0x401000
+            0xFF, 0xD0, // call eax
+            0xA3, 0x00, 0x00, 0x50, 0x00, // mov [0x500000], eax
+            0x31, 0xC0, 0xC2, 0x04, 0x00, // xor eax, eax; ret 4
+        ];
+        assert_eq!(stub, expected);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn build_call_stub_rejects_wide_x86_values() {
+        let wide = 0x1_0000_0000;
+
+        assert!(build_call_stub(wide, &[], CallingConvention::Stdcall, 0x500000).is_err());
+        assert!(build_call_stub(0x401000, &[wide], CallingConvention::Cdecl, 0x500000).is_err());
+        assert!(build_call_stub(0x401000, &[], CallingConvention::Fastcall, wide).is_err());
+        assert!(build_call_stub(wide, &[wide], CallingConvention::Win64, wide).is_ok());
+    }
+
+    #[cfg(feature = "disassembly")]
+    fn disassemble_stub(convention: CallingConvention, args: &[usize]) -> Vec<String> {
+        let bitness = match convention {
+            CallingConvention::Win64 => 64,
+            _ => 32,
+        };
+        let stub = build_call_stub(0x401000, args, convention, 0x500000).unwrap();
+
+        crate::asm::disasm::disassemble(&stub, 0, bitness).unwrap()
+    }
+
+    #[cfg(feature = "disassembly")]
+    #[test]
+    fn build_call_stub_x86_conventions_decode() {
+        let args = [1, 2, 3];
+
+        assert_eq!(
+            disassemble_stub(CallingConvention::Cdecl, &args),
+            [
+                "push 3", "push 2", "push 1", "mov eax,401000h", "call eax", "add esp,0Ch",
+                "mov [500000h],eax", "xor eax,eax", "ret 4",
+            ]
+        );
+        assert_eq!(
+            disassemble_stub(CallingConvention::Stdcall, &args),
+            [
+                "push 3", "push 2", "push 1", "mov eax,401000h", "call eax", "mov [500000h],eax",
+                "xor eax,eax", "ret 4",
+            ]
+        );
+        assert_eq!(
+            disassemble_stub(CallingConvention::Fastcall, &args),
+            [
+                "push 3", "mov ecx,1", "mov edx,2", "mov eax,401000h", "call eax",
+                "mov [500000h],eax", "xor eax,eax", "ret 4",
+            ]
+        );
+    }
+
+    #[cfg(feature = "disassembly")]
+    #[test]
+    fn build_call_stub_win64_decodes() {
+        assert_eq!(
+            disassemble_stub(CallingConvention::Win64, &[1, 2, 3, 4, 5, 6]),
+            [
+                "sub rsp,38h", "mov rax,5", "mov [rsp+20h],rax", "mov rax,6", "mov [rsp+28h],rax",
+                "mov rcx,1", "mov rdx,2", "mov r8,3", "mov r9,4", "mov rax,401000h", "call rax",
+                "mov [qword 500000h],rax", "add rsp,38h", "xor eax,eax", "ret",
+            ]
+        );
+        // Without stack arguments only the shadow space is reserved, still realigning rsp.
+        assert_eq!(
+            disassemble_stub(CallingConvention::Win64, &[1])[..2],
+            ["sub rsp,28h", "mov rcx,1"]
+        );
+    }
+
+    /// Spawns a `cmd` that runs until its stdin is closed.
+    fn spawn_child() -> std::process::Child {
+        std::process::Command::new("cmd")
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap()
+    }
+
+    /// Closes the child's stdin and waits for it to exit.
+    fn exit_child(child: &mut std::process::Child) {
+        drop(child.stdin.take());
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn execute_shellcode_child_returns_exit_code() {
+        let mut child = spawn_child();
+        let handle = open_process(child.id()).unwrap();
+
+        // mov eax, 0x2A; ret (ret 4 for the stdcall thread routine on x86)
+        let mut code = vec![0xB8, 0x2A, 0x00, 0x00, 0x00];
+        if cfg!(target_pointer_width = "64") {
+            code.push(0xC3);
+        } else {
+            code.extend([0xC2, 0x04, 0x00]);
+        }
+        let exit_code = execute_shellcode(&handle, &code, Some(Duration::from_secs(5)));
+
+        exit_child(&mut child);
+        assert_eq!(exit_code.unwrap(), 0x2A);
+    }
+
+    #[test]
+    fn call_remote_child_get_current_process_id() {
+        let mut child = spawn_child();
+        let handle = open_process(child.id()).unwrap();
+
+        // The loader may not have mapped kernel32 yet right after the spawn.
+        let kernel32 = (0..50)
+            .find_map(|_| {
+                let module = module_by_name(&handle, "kernel32.dll", true, None).ok().flatten();
+                if module.is_none() {
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                module
+            })
+            .unwrap();
+        let func = get_export_address(&handle, kernel32, "GetCurrentProcessId", None).unwrap();
+        let convention = if cfg!(target_pointer_width = "64") {
+            CallingConvention::Win64
+        } else {
+            CallingConvention::Stdcall
+        };
+        let pid = call_remote(&handle, func, &[], convention, None);
+
+        exit_child(&mut child);
+        assert_eq!(pid.unwrap(), child.id() as usize);
+    }
+}
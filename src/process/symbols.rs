@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use pdb::FallibleIterator;
+
+use crate::{memop_err, MemOpError, MemOpResult};
+
+/// Resolves the address of a symbol in a loaded module through the module's PDB.
+///
+/// Public symbols (functions and exported data) and global data symbols are searched by their
+/// (possibly decorated) name. Where symbols are available, this replaces pattern scanning.
+///
+/// # Arguments
+/// * `module_base` - Base address of the module in the target, e.g. `lpBaseOfDll`
+/// * `pdb_path` - Path to the PDB matching the loaded module
+/// * `symbol_name` - Name of the symbol as stored in the PDB
+///
+/// # Returns
+/// Returns `module_base` plus the RVA of the symbol
+///
+/// # Example
+/// ```rust,norun
+/// let info = get_module_info(&handle, module, None)?;
+/// let addr = resolve_symbol(info.lpBaseOfDll as usize, "game.pdb", "Player::TakeDamage")?;
+/// ```
+pub fn resolve_symbol(
+    module_base: usize,
+    pdb_path: impl AsRef<Path>,
+    symbol_name: &str,
+) -> MemOpResult<usize> {
+    let file = std::fs::File::open(pdb_path.as_ref()).map_err(MemOpError::new)?;
+    let mut pdb = pdb::PDB::open(file).map_err(MemOpError::new)?;
+
+    let address_map = pdb.address_map().map_err(MemOpError::new)?;
+    let symbol_table = pdb.global_symbols().map_err(MemOpError::new)?;
+
+    let mut symbols = symbol_table.iter();
+    while let Some(symbol) = symbols.next().map_err(MemOpError::new)? {
+        // Symbol kinds the pdb crate can't parse are irrelevant here.
+        let (name, offset) = match symbol.parse() {
+            Ok(pdb::SymbolData::Public(data)) => (data.name, data.offset),
+            Ok(pdb::SymbolData::Data(data)) => (data.name, data.offset),
+            _ => continue,
+        };
+
+        if name.to_string() != symbol_name {
+            continue;
+        }
+
+        let rva = offset.to_rva(&address_map).ok_or(memop_err!(
+            "Symbol \"{symbol_name}\" has no RVA in the PDB's address map."
+        ))?;
+
+        let rva = rva.0 as usize;
+        return module_base.checked_add(rva).ok_or(memop_err!(
+            "Symbol \"{symbol_name}\" at RVA \"{rva:#X}\" overflows module base \"{module_base:#X}\"."
+        ));
+    }
+
+    Err(memop_err!("No symbol named \"{symbol_name}\" in the PDB."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/symbols.pdb");
+
+    #[test]
+    fn resolve_symbol_public_function_and_data() {
+        let base = 0x180000000;
+
+        assert_eq!(resolve_symbol(base, FIXTURE, "zhol_fixture_function").unwrap(), base + 0x1000);
+        assert_eq!(resolve_symbol(base, FIXTURE, "zhol_fixture_data").unwrap(), base + 0x3000);
+    }
+
+    #[test]
+    fn resolve_symbol_missing_name() {
+        for name in ["zhol_fixture_missing", "zhol_fixture"] {
+            let err = resolve_symbol(0x180000000, FIXTURE, name).unwrap_err();
+            assert!(err.to_string().contains("No symbol named"), "{err}");
+        }
+    }
+
+    #[test]
+    fn resolve_symbol_base_overflow() {
+        let err = resolve_symbol(usize::MAX, FIXTURE, "zhol_fixture_function").unwrap_err();
+        assert!(err.to_string().contains("overflows"), "{err}");
+    }
+}
@@ -0,0 +1,330 @@
+use std::time::Duration;
+
+use windows::Win32::Foundation::{
+    CloseHandle, GetLastError, BOOL, ERROR_NOT_ALL_ASSIGNED, HANDLE, HMODULE, LUID,
+};
+use windows::Win32::Security::{
+    AdjustTokenPrivileges, LookupPrivilegeValueW, LUID_AND_ATTRIBUTES, SE_DEBUG_NAME,
+    SE_PRIVILEGE_ENABLED, TOKEN_ADJUST_PRIVILEGES, TOKEN_PRIVILEGES, TOKEN_QUERY,
+};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+};
+use windows::Win32::System::ProcessStatus::MODULEINFO;
+use windows::Win32::System::Threading::{GetCurrentProcess, IsWow64Process, OpenProcessToken};
+
+use crate::memory::read::read_bytes;
+use crate::memory::timeout::TimeoutPolicy;
+use crate::memory::write::write_bytes;
+use crate::process::module::get_named_modules;
+use crate::process::pattern::{find_pattern_offsets, Pattern};
+use crate::process::{open_process, SafeHandle};
+use crate::{memop_err, with_handle, MemOpError, MemOpResult, MemOpResultExt};
+
+/// Timeout used for the handle acquisitions made while attaching.
+const ATTACH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Identifies the process `attach()` should open.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttachTarget {
+    Pid(u32),
+    /// Executable name, e.g. `game.exe`, matched case-insensitively
+    Name(String),
+}
+
+impl From<u32> for AttachTarget {
+    fn from(pid: u32) -> Self {
+        AttachTarget::Pid(pid)
+    }
+}
+
+impl From<&str> for AttachTarget {
+    fn from(name: &str) -> Self {
+        AttachTarget::Name(name.to_string())
+    }
+}
+
+impl From<String> for AttachTarget {
+    fn from(name: String) -> Self {
+        AttachTarget::Name(name)
+    }
+}
+
+/// A process opened through `attach()`, with everything needed to start working on it.
+#[derive(Clone)]
+pub struct Target {
+    pub handle: SafeHandle,
+    pub pid: u32,
+    /// Whether the process runs as 32-bit (natively or under WOW64)
+    pub is_32bit: bool,
+    /// Full path of the main module
+    pub main_module_name: String,
+    pub main_module: HMODULE,
+    pub main_module_info: MODULEINFO,
+    /// Timeouts used by the operations on this target that don't specify their own
+    pub timeouts: TimeoutPolicy,
+}
+
+impl Target {
+    /// Reads bytes from the target, falling back to the policy's read timeout.
+    ///
+    /// # Example
+    /// ```rust,norun
+    /// let bytes = target.read_bytes(addr, 0x10, None)?;
+    /// let bytes = target.read_bytes(addr, 0x10, Duration::from_millis(50))?;
+    /// ```
+    pub fn read_bytes(
+        &self,
+        addr: usize,
+        size: usize,
+        timeout: impl Into<Option<Duration>>,
+    ) -> MemOpResult<Vec<u8>> {
+        read_bytes(&self.handle, addr, size, self.timeouts.read(timeout))
+    }
+
+    /// Writes bytes to the target, falling back to the policy's write timeout.
+    pub fn write_bytes(
+        &self,
+        addr: usize,
+        bytes: &[u8],
+        timeout: impl Into<Option<Duration>>,
+    ) -> MemOpResult<()> {
+        write_bytes(&self.handle, addr, bytes, self.timeouts.write(timeout))
+    }
+
+    /// Scans `size` bytes from `origin` for an IDA pattern, falling back to the policy's scan timeout.
+    ///
+    /// # Returns
+    /// Returns the absolute address of every match
+    pub fn scan(
+        &self,
+        pattern: impl Into<Pattern>,
+        origin: usize,
+        size: usize,
+        timeout: impl Into<Option<Duration>>,
+    ) -> MemOpResult<Vec<usize>> {
+        let bytes = read_bytes(&self.handle, origin, size, self.timeouts.scan(timeout))?;
+        let matches = find_pattern_offsets(&bytes, pattern.into().bytes());
+
+        Ok(matches.into_iter().map(|offset| origin + offset).collect())
+    }
+}
+
+/// Opens a process by name or PID and resolves its bitness and main module.
+///
+/// This is the recommended entry point, bundling `enable_debug_privilege()`, `open_process()`
+/// and `is_process_32bit()`. Failing to enable the debug privilege is not an error, as most targets
+/// don't need it; every other step fails with an error naming it.
+///
+/// # Arguments
+///
+/// * `target` - The PID (`u32`) or executable name (`&str`/`String`) of the process
+///
+/// # Examples
+///
+/// ```rust,norun
+/// use zhol::process::attach;
+///
+/// let target = attach("game.exe")?;
+/// println!("{} is 32-bit: {}", target.pid, target.is_32bit);
+/// ```
+pub fn attach(target: impl Into<AttachTarget>) -> MemOpResult<Target> {
+    let pid = match target.into() {
+        AttachTarget::Pid(pid) => pid,
+        AttachTarget::Name(name) => find_process_by_name(&name)
+            .with_context(|| format!("attach(): looking up process \"{name}\""))?,
+    };
+
+    _ = enable_debug_privilege();
+
+    let handle =
+        open_process(pid).with_context(|| format!("attach(): opening process \"{pid}\""))?;
+
+    let is_32bit = is_process_32bit(&handle)
+        .with_context(|| format!("attach(): querying bitness of process \"{pid}\""))?;
+
+    let (main_module_name, main_module, main_module_info) =
+        get_named_modules(&handle, Some(ATTACH_TIMEOUT))
+            .with_context(|| format!("attach(): enumerating modules of process \"{pid}\""))?
+            .into_iter()
+            .next()
+            .ok_or(memop_err!("attach(): process \"{pid}\" has no modules."))?;
+
+    Ok(Target {
+        handle,
+        pid,
+        is_32bit,
+        main_module_name,
+        main_module,
+        main_module_info,
+        timeouts: TimeoutPolicy::default(),
+    })
+}
+
+/// Returns the PID of the first running process whose executable name matches, ignoring case.
+pub fn find_process_by_name(name: &str) -> MemOpResult<u32> {
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)? };
+
+    let mut entry = PROCESSENTRY32W {
+        dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+        ..Default::default()
+    };
+
+    let mut found = None;
+    let mut next = unsafe { Process32FirstW(snapshot, &mut entry) };
+    while next.is_ok() {
+        let length = entry
+            .szExeFile
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(entry.szExeFile.len());
+
+        if String::from_utf16_lossy(&entry.szExeFile[..length]).eq_ignore_ascii_case(name) {
+            found = Some(entry.th32ProcessID);
+            break;
+        }
+
+        next = unsafe { Process32NextW(snapshot, &mut entry) };
+    }
+
+    _ = unsafe { CloseHandle(snapshot) };
+
+    found.ok_or(memop_err!("No running process named \"{name}\"."))
+}
+
+/// Enables `SeDebugPrivilege` for the current process, allowing it to open processes of other users.
+///
+/// Errors if the privilege could not be assigned, which usually means the process isn't elevated.
+pub fn enable_debug_privilege() -> MemOpResult<()> {
+    let mut token = HANDLE::default();
+    unsafe {
+        OpenProcessToken(
+            GetCurrentProcess(),
+            TOKEN_ADJUST_PRIVILEGES | TOKEN_QUERY,
+            &mut token,
+        )?
+    };
+
+    let result = (|| -> MemOpResult<()> {
+        let mut luid = LUID::default();
+        unsafe { LookupPrivilegeValueW(None, SE_DEBUG_NAME, &mut luid)? };
+
+        let privileges = TOKEN_PRIVILEGES {
+            PrivilegeCount: 1,
+            Privileges: [LUID_AND_ATTRIBUTES {
+                Luid: luid,
+                Attributes: SE_PRIVILEGE_ENABLED,
+            }],
+        };
+
+        unsafe { AdjustTokenPrivileges(token, false, Some(&privileges), 0, None, None)? };
+
+        // AdjustTokenPrivileges succeeds even when the privilege isn't held, only flagging it here.
+        if unsafe { GetLastError() } == ERROR_NOT_ALL_ASSIGNED {
+            return Err(memop_err!(
+                "SeDebugPrivilege is not held by the current process."
+            ));
+        }
+
+        Ok(())
+    })();
+
+    _ = unsafe { CloseHandle(token) };
+
+    result
+}
+
+/// Checks whether the process behind a handle runs as 32-bit.
+///
+/// WOW64 processes are 32-bit; otherwise the process matches the OS, which is 64-bit
+/// if this process is either 64-bit itself or running under WOW64.
+pub fn is_process_32bit(handle: &SafeHandle) -> MemOpResult<bool> {
+    let target_wow64 = with_handle!(handle, Some(ATTACH_TIMEOUT), |guard| -> bool, {
+        is_wow64(*guard)
+    })?;
+
+    if target_wow64 {
+        return Ok(true);
+    }
+
+    let os_64bit = cfg!(target_pointer_width = "64") || is_wow64(unsafe { GetCurrentProcess() })?;
+
+    Ok(!os_64bit)
+}
+
+fn is_wow64(process: HANDLE) -> MemOpResult<bool> {
+    let mut wow64 = BOOL::default();
+    unsafe { IsWow64Process(process, &mut wow64) }.map_err(MemOpError::from)?;
+
+    Ok(wow64.as_bool())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A target whose handle is held by the caller, so every operation on it times out acquiring it.
+    fn blocked_target(timeouts: TimeoutPolicy) -> Target {
+        Target {
+            handle: SafeHandle::with_pid(HANDLE::default(), None),
+            pid: 0,
+            is_32bit: true,
+            main_module_name: String::new(),
+            main_module: HMODULE::default(),
+            main_module_info: MODULEINFO::default(),
+            timeouts,
+        }
+    }
+
+    /// Returns the timeout an operation gave up after.
+    fn timeout_of<T>(result: MemOpResult<T>) -> Option<Duration> {
+        match result {
+            Err(MemOpError::TimeoutReached((timeout, _))) => timeout,
+            Err(e) => panic!("Expected a timeout, got \"{e}\"."),
+            Ok(_) => panic!("Expected a timeout, but the operation succeeded."),
+        }
+    }
+
+    #[test]
+    fn target_operations_fall_back_to_the_policy() {
+        let target = blocked_target(TimeoutPolicy {
+            read: Some(Duration::from_millis(10)),
+            write: Some(Duration::from_millis(20)),
+            scan: Some(Duration::from_millis(30)),
+            acquire: None,
+        });
+        let _guard = target.handle.acquire_with_timeout(None).unwrap();
+
+        let read = target.read_bytes(0x1000, 0x4, None);
+        let write = target.write_bytes(0x1000, &[0x90], None);
+        let scan = target.scan("90", 0x1000, 0x10, None);
+
+        assert_eq!(timeout_of(read), Some(Duration::from_millis(10)));
+        assert_eq!(timeout_of(write), Some(Duration::from_millis(20)));
+        assert_eq!(timeout_of(scan), Some(Duration::from_millis(30)));
+    }
+
+    #[test]
+    fn target_operations_prefer_explicit_timeouts() {
+        let target = blocked_target(TimeoutPolicy::bounded());
+        let _guard = target.handle.acquire_with_timeout(None).unwrap();
+        let explicit = Duration::from_millis(5);
+
+        assert_eq!(timeout_of(target.read_bytes(0x1000, 0x4, explicit)), Some(explicit));
+        assert_eq!(timeout_of(target.scan("90", 0x1000, 0x10, explicit)), Some(explicit));
+    }
+
+    #[test]
+    fn attach_current_process_by_pid() {
+        let target = attach(std::process::id()).unwrap();
+        let exe = std::env::current_exe().unwrap();
+        let exe_name = exe.file_name().unwrap().to_string_lossy().to_lowercase();
+
+        assert_eq!(target.pid, std::process::id());
+        assert_eq!(target.is_32bit, cfg!(target_pointer_width = "32"));
+        assert!(target.main_module_name.to_lowercase().ends_with(&exe_name));
+        assert_eq!(target.main_module_info.lpBaseOfDll, target.main_module.0);
+        assert!(target.main_module_info.SizeOfImage > 0);
+        assert_eq!(target.read_bytes(target.main_module.0 as usize, 2, None).unwrap(), b"MZ");
+    }
+}
@@ -0,0 +1,210 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+#[cfg(target_arch = "x86")]
+use windows::Win32::System::Diagnostics::Debug::CONTEXT_CONTROL_X86;
+use windows::Win32::System::Diagnostics::Debug::{GetThreadContext, CONTEXT};
+#[cfg(target_arch = "x86_64")]
+use windows::Win32::System::Diagnostics::Debug::{
+    Wow64GetThreadContext, CONTEXT_CONTROL_AMD64, WOW64_CONTEXT, WOW64_CONTEXT_CONTROL,
+};
+use windows::Win32::System::Threading::{
+    GetProcessIdOfThread, OpenThread, ResumeThread, SuspendThread, THREAD_ACCESS_RIGHTS,
+    THREAD_GET_CONTEXT, THREAD_QUERY_INFORMATION, THREAD_SUSPEND_RESUME,
+};
+
+use super::target::is_process_32bit;
+use super::{RawHandle, SafeHandle, SafeHandleGuard};
+use crate::memory::read::read_bytes;
+use crate::memory::utils::{get_last_error, query_memory};
+use crate::MemOpResult;
+
+/// Opens a thread by its TID.
+///
+/// # Arguments
+///
+/// * `tid` - The TID of the thread to open
+/// * `access` - The access rights to request, e.g. `THREAD_GET_CONTEXT | THREAD_SUSPEND_RESUME`
+///
+/// # Returns
+///
+/// Returns a `SafeThreadHandle` that closes the thread handle once its last clone is dropped.
+///
+/// # Examples
+///
+/// ```rust,norun
+/// use windows::Win32::System::Threading::THREAD_SUSPEND_RESUME;
+/// use zhol::process::open_thread;
+///
+/// let thread = open_thread(tid, THREAD_SUSPEND_RESUME)?;
+/// thread.lock_scope(Some(Duration::from_secs(1)), |guard| {
+///     unsafe { SuspendThread(**guard) };
+///     Ok(())
+/// })?;
+/// ```
+pub fn open_thread(tid: u32, access: THREAD_ACCESS_RIGHTS) -> MemOpResult<SafeThreadHandle> {
+    let handle = unsafe { OpenThread(access, false, tid)? };
+
+    Ok(SafeThreadHandle::new(handle, tid))
+}
+
+/// Closes the wrapped thread handle when dropped.
+struct OwnedThreadHandle(RawHandle);
+
+impl Drop for OwnedThreadHandle {
+    fn drop(&mut self) {
+        unsafe { _ = CloseHandle(self.0.as_handle()) };
+    }
+}
+
+/// A thread handle with the same timeout-based locking as `SafeHandle`.
+///
+/// Clones share the handle, which is closed once the last clone is dropped.
+#[derive(Clone)]
+pub struct SafeThreadHandle {
+    /// The locking wrapper, `SafeHandle::pid()` reports the owning process if the handle allows querying it
+    inner: SafeHandle,
+    /// The TID of the thread the handle was opened for
+    tid: u32,
+    /// Closes the handle once the last clone is dropped
+    _owner: Arc<OwnedThreadHandle>,
+}
+
+impl SafeThreadHandle {
+    /// Wraps an open thread handle, taking ownership of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The thread `HANDLE`, closed once the last clone is dropped
+    /// * `tid` - The TID of the thread
+    pub fn new(handle: HANDLE, tid: u32) -> Self {
+        // GetProcessIdOfThread returns 0 without THREAD_QUERY_(LIMITED_)INFORMATION access.
+        let pid = match unsafe { GetProcessIdOfThread(handle) } {
+            0 => None,
+            pid => Some(pid),
+        };
+
+        SafeThreadHandle {
+            inner: SafeHandle::with_pid(handle, pid),
+            tid,
+            _owner: Arc::new(OwnedThreadHandle(RawHandle::new(handle))),
+        }
+    }
+
+    /// Returns the TID of the thread this handle was opened for.
+    pub fn tid(&self) -> u32 {
+        self.tid
+    }
+
+    /// Returns the PID of the process owning the thread, if the handle's access allowed querying it.
+    pub fn pid(&self) -> Option<u32> {
+        self.inner.pid()
+    }
+
+    /// Attempts to acquire exclusive access to the handle, see `SafeHandle::acquire_with_timeout()`.
+    pub fn acquire_with_timeout(&self, timeout: Option<Duration>) -> Option<SafeHandleGuard<'_>> {
+        self.inner.acquire_with_timeout(timeout)
+    }
+
+    /// Returns how long the current holder has had the handle, see `SafeHandle::held_duration()`.
+    pub fn held_duration(&self) -> Option<Duration> {
+        self.inner.held_duration()
+    }
+
+    /// Acquires the handle once and runs a closure with it, see `SafeHandle::lock_scope()`.
+    pub fn lock_scope<T, F>(&self, timeout: Option<Duration>, f: F) -> MemOpResult<T>
+    where
+        F: FnOnce(&SafeHandleGuard<'_>) -> MemOpResult<T>,
+    {
+        self.inner.lock_scope(timeout, f)
+    }
+}
+
+/// Reads a thread's stack from its stack pointer upward, for scanning it for return addresses.
+///
+/// The thread is suspended only while its context is read, so the stack may change before it is read.
+/// The read stops at the end of the memory region the stack pointer lies in, which for stacks is the stack base.
+///
+/// # Arguments
+///
+/// * `handle` - A safe handle to the process owning the thread
+/// * `tid` - The TID of the thread
+/// * `max_bytes` - Maximum amount of bytes read from the stack pointer upward
+/// * `timeout` - Optional timeout for each handle acquisition
+///
+/// # Returns
+///
+/// Returns the stack pointer and the bytes read from it
+///
+/// # Examples
+///
+/// ```rust,norun
+/// let (sp, stack) = read_thread_stack(&handle, tid, 0x1000, None)?;
+/// for (i, word) in stack.chunks_exact(8).enumerate() {
+///     println!("{:#X}: {:#X}", sp + i * 8, u64::from_le_bytes(word.try_into().unwrap()));
+/// }
+/// ```
+pub fn read_thread_stack(
+    handle: &SafeHandle,
+    tid: u32,
+    max_bytes: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<(usize, Vec<u8>)> {
+    let is_32bit = is_process_32bit(handle)?;
+    let thread = open_thread(
+        tid,
+        THREAD_GET_CONTEXT | THREAD_SUSPEND_RESUME | THREAD_QUERY_INFORMATION,
+    )?;
+
+    let sp = thread.lock_scope(timeout, |guard| {
+        if unsafe { SuspendThread(**guard) } == u32::MAX {
+            return Err(get_last_error());
+        }
+
+        let sp = stack_pointer(**guard, is_32bit);
+        unsafe { ResumeThread(**guard) };
+
+        sp
+    })?;
+
+    let mbi = query_memory(handle, sp, timeout)?;
+    let region_end = mbi.BaseAddress as usize + mbi.RegionSize;
+    let stack = read_bytes(handle, sp, max_bytes.min(region_end - sp), timeout)?;
+
+    Ok((sp, stack))
+}
+
+/// Reads the stack pointer from the context of a suspended thread.
+#[cfg(target_arch = "x86_64")]
+fn stack_pointer(thread: HANDLE, is_32bit: bool) -> MemOpResult<usize> {
+    if is_32bit {
+        let mut context = WOW64_CONTEXT {
+            ContextFlags: WOW64_CONTEXT_CONTROL,
+            ..Default::default()
+        };
+        unsafe { Wow64GetThreadContext(thread, &mut context)? };
+
+        return Ok(context.Esp as usize);
+    }
+
+    let mut context = CONTEXT {
+        ContextFlags: CONTEXT_CONTROL_AMD64,
+        ..Default::default()
+    };
+    unsafe { GetThreadContext(thread, &mut context)? };
+
+    Ok(context.Rsp as usize)
+}
+
+/// Reads the stack pointer from the context of a suspended thread.
+#[cfg(target_arch = "x86")]
+fn stack_pointer(thread: HANDLE, _is_32bit: bool) -> MemOpResult<usize> {
+    let mut context = CONTEXT {
+        ContextFlags: CONTEXT_CONTROL_X86,
+        ..Default::default()
+    };
+    unsafe { GetThreadContext(thread, &mut context)? };
+
+    Ok(context.Esp as usize)
+}
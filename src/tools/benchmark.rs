@@ -0,0 +1,160 @@
+use std::time::{Duration, Instant};
+
+use windows::Win32::System::Memory::PAGE_EXECUTE_READWRITE;
+
+use crate::memory::read::read_bytes;
+use crate::memory::utils::ProtectionGuard;
+use crate::memory::write::write_bytes;
+use crate::process::SafeHandle;
+use crate::{memop_err, MemOpResult};
+
+/// Latency distribution of one kind of memory operation, see `benchmark_access()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub min: Duration,
+    pub max: Duration,
+    pub avg: Duration,
+    /// 99th percentile, the latency a timeout should comfortably exceed
+    pub p99: Duration,
+}
+
+impl LatencyStats {
+    /// Computes the distribution of a set of measured latencies, all zero if there are none.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return LatencyStats::default();
+        }
+
+        samples.sort_unstable();
+        let total: Duration = samples.iter().sum();
+        let p99_index = (samples.len() * 99).div_ceil(100) - 1;
+
+        let avg_nanos = total.as_nanos() / samples.len() as u128;
+
+        LatencyStats {
+            min: samples[0],
+            max: samples[samples.len() - 1],
+            avg: Duration::from_nanos(u64::try_from(avg_nanos).unwrap_or(u64::MAX)),
+            p99: samples[p99_index],
+        }
+    }
+}
+
+/// Read and write latencies measured by `benchmark_access()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccessStats {
+    pub reads: LatencyStats,
+    pub writes: LatencyStats,
+}
+
+/// Measures read and write latency against the target, for picking sensible timeouts.
+///
+/// Each iteration reads `size` bytes at `addr` and writes the same bytes back. A change the target makes
+/// in between is reverted by that write, so point it at memory the target doesn't write to, e.g. var_mem
+/// or a region from `allocate_memory()`. Times are taken with the monotonic `Instant`.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `addr` - Address to access, e.g. somewhere in var_mem
+/// * `size` - Size (in bytes) of each access
+/// * `iterations` - Amount of reads and of writes measured
+/// * `exclude_protection` - Make the range writable once up front, so the writes measured skip the protect/restore pair
+///
+/// # Example
+/// ```rust,norun
+/// let stats = benchmark_access(&handle, health_addr, 0x4, 1000, true)?;
+/// hook.set_timeouts(TimeoutPolicy {
+///     read: Some(stats.reads.p99 * 10),
+///     write: Some(stats.writes.p99 * 10),
+///     ..hook.timeouts()
+/// });
+/// ```
+pub fn benchmark_access(
+    handle: &SafeHandle,
+    addr: usize,
+    size: usize,
+    iterations: usize,
+    exclude_protection: bool,
+) -> MemOpResult<AccessStats> {
+    if iterations == 0 {
+        return Err(memop_err!("Benchmark needs at least one iteration."));
+    }
+
+    let protection = match exclude_protection {
+        true => Some(ProtectionGuard::apply(
+            handle,
+            addr,
+            size,
+            PAGE_EXECUTE_READWRITE,
+            None,
+        )?),
+        false => None,
+    };
+
+    let mut reads = Vec::with_capacity(iterations);
+    let mut writes = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let bytes = read_bytes(handle, addr, size, None)?;
+        reads.push(start.elapsed());
+
+        let start = Instant::now();
+        write_bytes(handle, addr, &bytes, None)?;
+        writes.push(start.elapsed());
+    }
+
+    if let Some(protection) = protection {
+        protection.restore()?;
+    }
+
+    Ok(AccessStats {
+        reads: LatencyStats::from_samples(reads),
+        writes: LatencyStats::from_samples(writes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_samples_of_nothing_is_zero() {
+        assert_eq!(LatencyStats::from_samples(Vec::new()), LatencyStats::default());
+    }
+
+    #[test]
+    fn from_samples_sorts_and_averages() {
+        let samples = [30, 10, 20].map(Duration::from_micros).to_vec();
+
+        let stats = LatencyStats::from_samples(samples);
+
+        assert_eq!(stats.min, Duration::from_micros(10));
+        assert_eq!(stats.max, Duration::from_micros(30));
+        assert_eq!(stats.avg, Duration::from_micros(20));
+        assert_eq!(stats.p99, Duration::from_micros(30));
+    }
+
+    #[test]
+    fn from_samples_p99_skips_the_slowest_percent() {
+        let samples: Vec<Duration> = (1..=200).rev().map(Duration::from_millis).collect();
+
+        let stats = LatencyStats::from_samples(samples);
+
+        assert_eq!(stats.p99, Duration::from_millis(198));
+        assert_eq!(stats.avg, Duration::from_micros(100_500));
+    }
+
+    #[test]
+    fn benchmark_access_current_process() {
+        let handle = crate::process::open_process(std::process::id()).unwrap();
+        let buffer = vec![0xA5u8; 0x40].into_boxed_slice();
+
+        let stats = benchmark_access(&handle, buffer.as_ptr() as usize, buffer.len(), 0x10, false).unwrap();
+
+        assert!(stats.reads.min > Duration::ZERO);
+        assert!(stats.writes.min > Duration::ZERO);
+        assert!(stats.reads.p99 <= stats.reads.max);
+        assert!(buffer.iter().all(|&b| b == 0xA5));
+    }
+}
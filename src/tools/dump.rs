@@ -0,0 +1,171 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::memory::read::read_bytes;
+use crate::memory::utils::readable_regions;
+use crate::process::SafeHandle;
+use crate::{MemOpError, MemOpResult};
+
+/// A region written to a dump by `dump_process()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DumpEntry {
+    /// Address of the region in the target
+    pub addr: usize,
+    /// Size of the region, as stored in the dump
+    pub size: usize,
+    /// Offset of the region's first byte in the dump file
+    pub offset: u64,
+}
+
+/// Maps the regions of a dump written by `dump_process()` to their offsets in the file.
+///
+/// Regions are stored back to back in address order, so both directions are a binary search.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DumpManifest {
+    pub entries: Vec<DumpEntry>,
+}
+
+impl DumpManifest {
+    /// Returns the file offset holding the byte at `addr`, if it was dumped.
+    pub fn offset_of(&self, addr: usize) -> Option<u64> {
+        let i = self.entries.partition_point(|e| e.addr + e.size <= addr);
+        let entry = self.entries.get(i).filter(|e| e.addr <= addr)?;
+
+        Some(entry.offset + (addr - entry.addr) as u64)
+    }
+
+    /// Returns the address the byte at file offset `offset` was dumped from.
+    ///
+    /// Converts matches of e.g. `scan_reader()` over the dump file back to addresses.
+    pub fn addr_of(&self, offset: u64) -> Option<usize> {
+        let i = self.entries.partition_point(|e| e.offset + e.size as u64 <= offset);
+        let entry = self.entries.get(i).filter(|e| e.offset <= offset)?;
+
+        Some(entry.addr + (offset - entry.offset) as usize)
+    }
+
+    /// Total amount of bytes in the dump.
+    pub fn len(&self) -> u64 {
+        self.entries.iter().map(|e| e.size as u64).sum()
+    }
+
+    /// Returns whether no region was dumped.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Dumps every committed, readable region of the target into a single file for offline analysis.
+///
+/// Regions that fail to read (e.g. freed while dumping) are skipped and left out of the manifest.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `out_path` - Path of the dump file, overwritten if it exists
+///
+/// # Returns
+/// Returns the manifest mapping dumped addresses to file offsets
+///
+/// # Example
+/// ```rust,norun
+/// let manifest = dump_process(&handle, "game.dmp")?;
+/// let file = std::fs::File::open("game.dmp")?;
+/// for offset in scan_reader(file, "48 8B 05 ?? ?? ?? ??")? {
+///     println!("{:#X}", manifest.addr_of(offset as u64).unwrap());
+/// }
+/// ```
+pub fn dump_process(handle: &SafeHandle, out_path: impl AsRef<Path>) -> MemOpResult<DumpManifest> {
+    let file = File::create(out_path.as_ref()).map_err(MemOpError::new)?;
+    let mut writer = BufWriter::new(file);
+    let mut manifest = DumpManifest::default();
+    let mut offset = 0u64;
+
+    for mbi in readable_regions(handle, None)? {
+        let addr = mbi.BaseAddress as usize;
+        let Ok(bytes) = read_bytes(handle, addr, mbi.RegionSize, None) else {
+            continue;
+        };
+
+        writer.write_all(&bytes).map_err(MemOpError::new)?;
+        manifest.entries.push(DumpEntry {
+            addr,
+            size: bytes.len(),
+            offset,
+        });
+        offset += bytes.len() as u64;
+    }
+
+    writer.flush().map_err(MemOpError::new)?;
+
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Three regions with gaps between them in the address space, back to back in the file.
+    fn manifest() -> DumpManifest {
+        DumpManifest {
+            entries: [
+                (0x1000, 0x1000, 0),
+                (0x5000, 0x2000, 0x1000),
+                (0x10000, 0x10, 0x3000),
+            ]
+            .map(|(addr, size, offset)| DumpEntry { addr, size, offset })
+            .to_vec(),
+        }
+    }
+
+    #[test]
+    fn offset_of_maps_dumped_bytes() {
+        let manifest = manifest();
+
+        assert_eq!(manifest.offset_of(0x1000), Some(0));
+        assert_eq!(manifest.offset_of(0x1FFF), Some(0xFFF));
+        assert_eq!(manifest.offset_of(0x5000), Some(0x1000));
+        assert_eq!(manifest.offset_of(0x6FFF), Some(0x2FFF));
+        assert_eq!(manifest.offset_of(0x1000F), Some(0x300F));
+    }
+
+    #[test]
+    fn offset_of_skips_gaps() {
+        let manifest = manifest();
+
+        assert_eq!(manifest.offset_of(0xFFF), None);
+        assert_eq!(manifest.offset_of(0x2000), None);
+        assert_eq!(manifest.offset_of(0x4FFF), None);
+        assert_eq!(manifest.offset_of(0x7000), None);
+        assert_eq!(manifest.offset_of(0x10010), None);
+        assert_eq!(DumpManifest::default().offset_of(0x1000), None);
+    }
+
+    #[test]
+    fn addr_of_maps_file_offsets() {
+        let manifest = manifest();
+
+        assert_eq!(manifest.addr_of(0), Some(0x1000));
+        assert_eq!(manifest.addr_of(0xFFF), Some(0x1FFF));
+        assert_eq!(manifest.addr_of(0x1000), Some(0x5000));
+        assert_eq!(manifest.addr_of(0x300F), Some(0x1000F));
+        assert_eq!(manifest.addr_of(manifest.len()), None);
+    }
+
+    #[test]
+    fn offset_of_and_addr_of_round_trip() {
+        let manifest = manifest();
+
+        for entry in &manifest.entries {
+            for addr in [entry.addr, entry.addr + 1, entry.addr + entry.size - 1] {
+                let offset = manifest.offset_of(addr).unwrap();
+                assert_eq!(manifest.addr_of(offset), Some(addr));
+            }
+        }
+        for offset in 0..manifest.len() {
+            let addr = manifest.addr_of(offset).unwrap();
+            assert_eq!(manifest.offset_of(addr), Some(offset));
+        }
+        assert_eq!(manifest.len(), 0x3010);
+    }
+}
@@ -0,0 +1,787 @@
+pub mod benchmark;
+pub mod dump;
+pub mod signatures;
+
+pub use benchmark::{benchmark_access, AccessStats, LatencyStats};
+pub use dump::{dump_process, DumpEntry, DumpManifest};
+pub use signatures::{assess_signature, assess_signature_in_bytes, SignatureDef, SignatureQuality};
+#[cfg(feature = "signatures")]
+pub use signatures::{load_signatures, parse_signatures, parse_signatures_json};
+
+use crate::memory::read::read_bytes;
+use crate::process::module::{get_module_info, get_module_sections};
+use crate::process::pattern::{find_pattern_offsets, Pattern};
+use crate::process::pe;
+use crate::process::SafeHandle;
+use crate::{memop_err, MemOpError, MemOpResult};
+
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+use windows::Win32::Foundation::HMODULE;
+
+/// Size of the chunks `scan_reader()` reads at a time.
+const SCAN_CHUNK_SIZE: usize = 0x10000;
+
+/// Scans process memory for code referencing an absolute address.
+///
+/// See `find_references_in_bytes()` for what counts as a reference.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `target_addr` - The address references are searched for, e.g. a global
+/// * `origin` - Address to begin searching at, usually the start of `.text`
+/// * `size` - Size (in bytes) of the search area
+///
+/// # Returns
+/// Returns the address of every referencing operand
+///
+/// # Example
+/// ```rust,norun
+/// let refs = find_references_to(&handle, global_addr, text_start, text_size)?;
+/// ```
+pub fn find_references_to(
+    handle: &SafeHandle,
+    target_addr: usize,
+    origin: usize,
+    size: usize,
+) -> MemOpResult<Vec<usize>> {
+    let bytes = read_bytes(handle, origin, size, None)?;
+
+    Ok(find_references_in_bytes(&bytes, origin, target_addr))
+}
+
+/// Finds operands in a code buffer that reference `target_addr`.
+///
+/// An operand references the target if it is either:
+/// * an absolute 32-bit (or, for targets above 4GB, 64-bit) address equal to it, as in x86 `mov`/`lea`/`push`
+/// * a rel32 displacement resolving to it, as in `call`/`jmp`/`jcc` or x64 RIP-relative operands.
+///   Displacements are resolved as if they end their instruction, which holds for all of these
+///   unless an immediate follows the displacement.
+///
+/// No instructions are decoded, so data that happens to match is reported too.
+///
+/// # Arguments
+/// * `bytes` - The code to search
+/// * `base` - Address `bytes` were read from
+/// * `target_addr` - The address references are searched for
+///
+/// # Returns
+/// Returns the address of the first byte of every referencing operand
+pub fn find_references_in_bytes(bytes: &[u8], base: usize, target_addr: usize) -> Vec<usize> {
+    let mut references = Vec::new();
+    let target = target_addr as u64;
+
+    for i in 0..bytes.len().saturating_sub(3) {
+        let operand_addr = base + i;
+        let dword = u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+
+        let absolute = match target > u32::MAX as u64 {
+            true => bytes
+                .get(i..i + 8)
+                .is_some_and(|q| u64::from_le_bytes(q.try_into().unwrap()) == target),
+            false => dword as u64 == target,
+        };
+
+        let relative = (operand_addr as u64 + 4).wrapping_add(dword as i32 as i64 as u64) == target;
+
+        if absolute || relative {
+            references.push(operand_addr);
+        }
+    }
+
+    references
+}
+
+/// Finds detours other tools placed at function prologues in a module's `.text`.
+///
+/// See `find_detours_in_bytes()` for what counts as a detour.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `module` - The module whose `.text` is searched
+///
+/// # Returns
+/// Returns `(hook site, jump target)` for every detour found
+///
+/// # Example
+/// ```rust,norun
+/// for (site, target) in detect_detours(&handle, module)? {
+///     println!("{site:#X} is detoured to {target:#X}");
+/// }
+/// ```
+pub fn detect_detours(handle: &SafeHandle, module: HMODULE) -> MemOpResult<Vec<(usize, usize)>> {
+    let info = get_module_info(handle, module, None)?;
+    let text = get_module_sections(handle, module, None)?
+        .into_iter()
+        .find(|s| s.name == ".text")
+        .ok_or(memop_err!("Module has no .text section."))?;
+
+    let base = module.0 as usize;
+    let text_range = text.rva_range();
+    let text_addr = base + text_range.start;
+    let bytes = read_bytes(handle, text_addr, text_range.len(), None)?;
+
+    Ok(find_detours_in_bytes(&bytes, text_addr, base..base + info.SizeOfImage as usize))
+}
+
+/// Finds `jmp rel32`/`call rel32` at function prologues of a code buffer that lead outside of a module.
+///
+/// No instructions are decoded, so a prologue is assumed wherever an instruction follows `int3`/`nop`
+/// padding or a `ret`, or at the start of the buffer. Functions without padding before them aren't checked.
+///
+/// # Arguments
+/// * `bytes` - The code to search
+/// * `base` - Address `bytes` were read from
+/// * `module_range` - Addresses of the module `bytes` belong to, jumps into it are ignored
+///
+/// # Returns
+/// Returns `(hook site, jump target)` for every detour found
+pub fn find_detours_in_bytes(
+    bytes: &[u8],
+    base: usize,
+    module_range: std::ops::Range<usize>,
+) -> Vec<(usize, usize)> {
+    let mut detours = Vec::new();
+
+    for i in 0..bytes.len().saturating_sub(4) {
+        let is_branch = matches!(bytes[i], 0xE8 | 0xE9);
+        let is_prologue = i == 0 || matches!(bytes[i - 1], 0xCC | 0x90 | 0xC3);
+        if !is_branch || !is_prologue {
+            continue;
+        }
+
+        let site = base + i;
+        let disp = i32::from_le_bytes([bytes[i + 1], bytes[i + 2], bytes[i + 3], bytes[i + 4]]);
+        let target = (site + 5).wrapping_add_signed(disp as isize);
+
+        if !module_range.contains(&target) {
+            detours.push((site, target));
+        }
+    }
+
+    detours
+}
+
+/// Scans a stream (file, dump, etc.) for an IDA pattern.
+///
+/// The last `pattern.len() - 1` bytes of each chunk are carried over to the next, so matches
+/// straddling chunk boundaries are found too.
+///
+/// # Arguments
+/// * `reader` - The stream to scan, read until EOF
+/// * `pattern` - IDA byte pattern
+///
+/// # Returns
+/// Returns the stream offset of every match
+///
+/// # Example
+/// ```rust,norun
+/// let file = std::io::BufReader::new(std::fs::File::open("game.exe")?);
+/// let offsets = scan_reader(file, "E8 ?? ?? ?? ?? 85 C0")?;
+/// ```
+pub fn scan_reader<R: Read>(mut reader: R, pattern: impl Into<Pattern>) -> MemOpResult<Vec<usize>> {
+    let pattern_bytes = pattern.into();
+    if pattern_bytes.is_empty() {
+        return Err(memop_err!("Cannot scan for an empty pattern."));
+    }
+
+    let carry_len = pattern_bytes.len() - 1;
+    let mut matches = Vec::new();
+    let mut window: Vec<u8> = Vec::with_capacity(carry_len + SCAN_CHUNK_SIZE);
+    // Stream offset of window[0]
+    let mut window_offset = 0usize;
+    let mut chunk = vec![0u8; SCAN_CHUNK_SIZE];
+
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(MemOpError::new(e)),
+        };
+        window.extend_from_slice(&chunk[..read]);
+        if window.len() < pattern_bytes.len() {
+            continue;
+        }
+
+        // Carried bytes are shorter than the pattern, so no match is found twice.
+        for offset in find_pattern_offsets(&window, pattern_bytes.bytes()) {
+            matches.push(window_offset + offset);
+        }
+
+        let keep_from = window.len().saturating_sub(carry_len);
+        window_offset += keep_from;
+        window.drain(..keep_from);
+    }
+
+    Ok(matches)
+}
+
+/// Compares a loaded module's `.text` against the module file on disk, to spot in-memory patches.
+///
+/// Relocations are not applied, so bytes the loader relocated (absolute addresses in code) show up
+/// as differences when the module wasn't loaded at its preferred base.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `module` - Handle to the loaded module
+/// * `file_path` - Path of the module file, e.g. from `get_named_modules()`
+///
+/// # Returns
+/// Returns `(rva, memory byte, file byte)` for every differing byte of `.text`
+///
+/// # Example
+/// ```rust,norun
+/// for (rva, memory, file) in diff_module_against_file(&handle, module, &path)? {
+///     println!("{rva:#X}: {file:02X} -> {memory:02X}");
+/// }
+/// ```
+pub fn diff_module_against_file(
+    handle: &SafeHandle,
+    module: HMODULE,
+    file_path: impl AsRef<Path>,
+) -> MemOpResult<Vec<(usize, u8, u8)>> {
+    let file = std::fs::read(file_path.as_ref()).map_err(MemOpError::new)?;
+    let (rva, file_text) = file_text_section(&file)?;
+
+    let memory_text = read_bytes(handle, module.0 as usize + rva, file_text.len(), None)?;
+
+    Ok(diff_bytes(&memory_text, file_text, rva))
+}
+
+/// Returns the RVA of a module file's `.text` and its bytes in the file, as far as they are mapped.
+fn file_text_section(file: &[u8]) -> MemOpResult<(usize, &[u8])> {
+    let text = pe::sections(file)?
+        .into_iter()
+        .find(|s| s.name == ".text")
+        .ok_or(memop_err!("Module file has no .text section."))?;
+
+    let size = text.virtual_size.min(text.raw_size);
+    let file_text = file
+        .get(text.raw_offset..text.raw_offset + size)
+        .ok_or(memop_err!("Module file is truncated within .text."))?;
+
+    Ok((text.virtual_address, file_text))
+}
+
+/// Returns `(base + offset, left byte, right byte)` for every offset at which two buffers differ.
+///
+/// Only the overlapping length is compared.
+pub fn diff_bytes(left: &[u8], right: &[u8], base: usize) -> Vec<(usize, u8, u8)> {
+    left.iter()
+        .zip(right)
+        .enumerate()
+        .filter(|(_, (l, r))| l != r)
+        .map(|(i, (l, r))| (base + i, *l, *r))
+        .collect()
+}
+
+/// Extracts printable strings from process memory, like the Unix `strings` tool.
+///
+/// See `find_strings_in_bytes()` for what counts as printable.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `origin` - Address to begin searching at
+/// * `size` - Size (in bytes) of the search area
+/// * `min_len` - Minimum amount of characters in a reported string
+/// * `timeout` - Optional timeout for the read
+///
+/// # Returns
+/// Returns the absolute address of every string, with the string itself
+pub fn find_strings(
+    handle: &SafeHandle,
+    origin: usize,
+    size: usize,
+    min_len: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<(usize, String)>> {
+    let bytes = read_bytes(handle, origin, size, timeout)?;
+
+    Ok(find_strings_in_bytes(&bytes, origin, min_len))
+}
+
+/// Finds runs of at least `min_len` printable characters in a buffer.
+///
+/// Printable characters are printable ASCII, tabs, and non-control UTF-8 encoded characters.
+///
+/// # Arguments
+/// * `bytes` - The buffer to search
+/// * `base` - Address `bytes` were read from
+/// * `min_len` - Minimum amount of characters in a reported string
+pub fn find_strings_in_bytes(bytes: &[u8], base: usize, min_len: usize) -> Vec<(usize, String)> {
+    let mut strings = Vec::new();
+    let mut run = String::new();
+    let mut run_start = 0;
+    let mut run_chars = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let width = match bytes[i] {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 0,
+        };
+
+        let decoded = bytes
+            .get(i..i + width.max(1))
+            .and_then(|b| std::str::from_utf8(b).ok())
+            .and_then(|s| s.chars().next())
+            .filter(|&c| c == '\t' || !c.is_control());
+
+        match decoded {
+            Some(c) => {
+                if run.is_empty() {
+                    run_start = i;
+                }
+                run.push(c);
+                run_chars += 1;
+                i += width;
+            }
+            None => {
+                if run_chars >= min_len.max(1) {
+                    strings.push((base + run_start, std::mem::take(&mut run)));
+                }
+                run.clear();
+                run_chars = 0;
+                i += 1;
+            }
+        }
+    }
+
+    if run_chars >= min_len.max(1) {
+        strings.push((base + run_start, run));
+    }
+
+    strings
+}
+
+/// Extracts printable UTF-16LE strings from process memory, e.g. UI text and names.
+///
+/// See `find_wide_strings_in_bytes()` for what counts as printable.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `origin` - Address to begin searching at
+/// * `size` - Size (in bytes) of the search area
+/// * `min_len` - Minimum amount of characters in a reported string
+/// * `timeout` - Optional timeout for the read
+///
+/// # Returns
+/// Returns the absolute address of every string, with the decoded string
+pub fn find_wide_strings(
+    handle: &SafeHandle,
+    origin: usize,
+    size: usize,
+    min_len: usize,
+    timeout: Option<Duration>,
+) -> MemOpResult<Vec<(usize, String)>> {
+    let bytes = read_bytes(handle, origin, size, timeout)?;
+
+    Ok(find_wide_strings_in_bytes(&bytes, origin, min_len))
+}
+
+/// Finds runs of at least `min_len` printable UTF-16LE characters in a buffer.
+///
+/// Only code units at 2-byte aligned addresses are considered, as compilers align wide strings.
+/// Printable characters are printable ASCII, tabs, and alphanumeric characters of any script, so most random
+/// data (which decodes to symbols or unpaired surrogates) ends a run.
+///
+/// # Arguments
+/// * `bytes` - The buffer to search
+/// * `base` - Address `bytes` were read from
+/// * `min_len` - Minimum amount of characters in a reported string
+pub fn find_wide_strings_in_bytes(
+    bytes: &[u8],
+    base: usize,
+    min_len: usize,
+) -> Vec<(usize, String)> {
+    let start = base % 2;
+    let units: Vec<u16> = bytes
+        .get(start..)
+        .unwrap_or_default()
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+
+    let mut strings = Vec::new();
+    let mut run = String::new();
+    let mut run_start = 0;
+    let mut run_chars = 0;
+    let mut i = 0;
+
+    while i < units.len() {
+        let end = (i + 2).min(units.len());
+        let decoded = char::decode_utf16(units[i..end].iter().copied())
+            .next()
+            .and_then(|c| c.ok())
+            .filter(|&c| c == '\t' || c == ' ' || c.is_ascii_graphic() || c.is_alphanumeric());
+
+        match decoded {
+            Some(c) => {
+                if run.is_empty() {
+                    run_start = i;
+                }
+                run.push(c);
+                run_chars += 1;
+                i += c.len_utf16();
+            }
+            None => {
+                if run_chars >= min_len.max(1) {
+                    strings.push((base + start + run_start * 2, std::mem::take(&mut run)));
+                }
+                run.clear();
+                run_chars = 0;
+                i += 1;
+            }
+        }
+    }
+
+    if run_chars >= min_len.max(1) {
+        strings.push((base + start + run_start * 2, run));
+    }
+
+    strings
+}
+
+/// Generates a signature from the code at an address, wildcarding operands likely to change between builds.
+///
+/// See `signature_from_bytes()` for which operands are wildcarded.
+///
+/// # Arguments
+/// * `handle` - A safe handle to the target process
+/// * `addr` - Address of the first instruction, e.g. a function's start
+/// * `len` - Amount of bytes to build the signature from
+/// * `bitness` - 32 or 64, matching the target process
+///
+/// # Example
+/// ```rust,norun
+/// let signature = generate_signature(&handle, func_addr, 0x20, 32)?;
+/// println!("{}", signature.iter().map(|b| b.map_or("??".into(), |b| format!("{b:02X}"))).collect::<Vec<_>>().join(" "));
+/// ```
+#[cfg(feature = "disassembly")]
+pub fn generate_signature(
+    handle: &SafeHandle,
+    addr: usize,
+    len: usize,
+    bitness: u32,
+) -> MemOpResult<Vec<crate::memory::Byte>> {
+    let bytes = read_bytes(handle, addr, len, None)?;
+    let image = crate::process::module::get_named_modules(handle, None)?
+        .into_iter()
+        .map(|(_, _, info)| {
+            let base = info.lpBaseOfDll as usize;
+            base..base + info.SizeOfImage as usize
+        })
+        .find(|image| image.contains(&addr));
+
+    signature_from_bytes(&bytes, addr, bitness, image)
+}
+
+/// Generates a signature from code, keeping opcodes concrete and wildcarding the bytes of
+/// branch displacements (`call`/`jmp`/`jcc`), RIP-relative displacements and absolute memory addresses.
+///
+/// Displacements of indexed operands wider than a byte (e.g. a jump table's `[eax*4 + table]`) are
+/// wildcarded as well, as are immediates and displacements holding an address inside `image`.
+/// A trailing instruction cut off by the end of `bytes` is left out of the signature.
+///
+/// # Arguments
+/// * `bytes` - The code to build the signature from
+/// * `ip` - Address `bytes` were read from
+/// * `bitness` - 16, 32 or 64
+/// * `image` - Address range of the module holding the code, if any
+#[cfg(feature = "disassembly")]
+pub fn signature_from_bytes(
+    bytes: &[u8],
+    ip: usize,
+    bitness: u32,
+    image: Option<std::ops::Range<usize>>,
+) -> MemOpResult<Vec<crate::memory::Byte>> {
+    use crate::memory::Byte;
+    use iced_x86::{Decoder, DecoderError, DecoderOptions, OpKind, Register};
+
+    let mut decoder = Decoder::try_with_ip(bitness, bytes, ip as u64, DecoderOptions::NONE)
+        .map_err(|e| memop_err!("Could not create decoder: {e}"))?;
+
+    let mut signature = Vec::new();
+    while decoder.can_decode() {
+        let instruction = decoder.decode();
+
+        if instruction.is_invalid() {
+            if decoder.last_error() == DecoderError::NoMoreBytes {
+                break;
+            }
+
+            return Err(memop_err!(
+                "Invalid instruction at \"{:#X}\".",
+                instruction.ip()
+            ));
+        }
+
+        let start = instruction.ip() as usize - ip;
+        let instruction_bytes = &bytes[start..start + instruction.len()];
+        let mut instruction_signature: Vec<Byte> = instruction_bytes.iter().map(|&b| Some(b)).collect();
+
+        let op_kinds: Vec<OpKind> = (0..instruction.op_count()).map(|i| instruction.op_kind(i)).collect();
+        let is_branch = op_kinds.iter().any(|kind| {
+            matches!(kind, OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64)
+        });
+        let is_memory = op_kinds.contains(&OpKind::Memory);
+        let is_absolute_memory = is_memory
+            && (instruction.is_ip_rel_memory_operand() || instruction.memory_base() == Register::None);
+
+        // Only valid for the instruction the decoder decoded last.
+        let offsets = decoder.get_constant_offsets(&instruction);
+        let in_image = |value: u64| {
+            image
+                .as_ref()
+                .is_some_and(|image| usize::try_from(value).is_ok_and(|value| image.contains(&value)))
+        };
+        let immediate = offsets.has_immediate().then(|| {
+            let raw = &instruction_bytes[offsets.immediate_offset()..][..offsets.immediate_size()];
+            raw.iter().rev().fold(0u64, |value, &b| (value << 8) | b as u64)
+        });
+
+        let wildcard_displacement = is_memory
+            && offsets.has_displacement()
+            && (is_absolute_memory
+                || (instruction.memory_index() != Register::None && offsets.displacement_size() > 1)
+                || in_image(instruction.memory_displacement64()));
+        // Branch displacements are reported as immediates.
+        let wildcard_immediate = immediate.is_some_and(|value| is_branch || in_image(value));
+
+        let mut wildcard = |offset: usize, size: usize| {
+            instruction_signature[offset..offset + size].fill(None);
+        };
+        if (is_branch || wildcard_displacement) && offsets.has_displacement() {
+            wildcard(offsets.displacement_offset(), offsets.displacement_size());
+        }
+        if wildcard_immediate {
+            wildcard(offsets.immediate_offset(), offsets.immediate_size());
+        }
+
+        signature.extend(instruction_signature);
+    }
+
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_strings_in_bytes_reports_runs_of_min_len() {
+        let bytes = b"\x00\x01abc\x00hi\x00\xC3\xA9t\xC3\xA9\x00";
+
+        let strings = find_strings_in_bytes(bytes, 0x1000, 3);
+
+        assert_eq!(
+            strings,
+            vec![(0x1002, "abc".to_string()), (0x1009, "\u{E9}t\u{E9}".to_string())]
+        );
+    }
+
+    #[test]
+    fn find_strings_in_bytes_ends_runs_at_invalid_utf8() {
+        let bytes = b"ab\xFFcd\tef";
+
+        assert_eq!(
+            find_strings_in_bytes(bytes, 0x0, 2),
+            vec![(0x0, "ab".to_string()), (0x3, "cd\tef".to_string())]
+        );
+        assert_eq!(find_strings_in_bytes(bytes, 0x0, 0).len(), 2);
+    }
+
+    #[test]
+    fn scan_reader_finds_matches_across_chunks() {
+        let pattern = [0xDE, 0xAD, 0xBE, 0xEF];
+        let mut stream = vec![0u8; SCAN_CHUNK_SIZE * 3];
+        let offsets = [
+            0,
+            SCAN_CHUNK_SIZE - 4,
+            SCAN_CHUNK_SIZE * 2 - 1,
+            SCAN_CHUNK_SIZE * 3 - 4,
+        ];
+        for offset in offsets {
+            stream[offset..offset + 4].copy_from_slice(&pattern);
+        }
+
+        let matches = scan_reader(std::io::Cursor::new(&stream), "DE AD ?? EF").unwrap();
+
+        assert_eq!(matches, offsets);
+    }
+
+    #[test]
+    fn scan_reader_rejects_empty_patterns() {
+        assert!(scan_reader(std::io::Cursor::new([0u8; 4]), "").is_err());
+    }
+
+    #[test]
+    fn find_references_in_bytes_finds_relative_and_absolute_operands() {
+        #[rustfmt::skip]
+        let code = [
+            0xE8, 0xFB, 0x3F, 0x00, 0x00, // call 0x405000
+            0xA1, 0x00, 0x50, 0x40, 0x00, // mov eax, [0x405000]
+            0x8B, 0x0D, 0x00, 0x50, 0x40, 0x00, // mov ecx, [0x405000]
+            0xB8, 0x04, 0x50, 0x40, 0x00, // mov eax, 0x405004
+        ];
+
+        let references = find_references_in_bytes(&code, 0x401000, 0x405000);
+
+        assert_eq!(references, vec![0x401001, 0x401006, 0x40100C]);
+    }
+
+    #[test]
+    fn find_references_in_bytes_matches_64_bit_addresses() {
+        #[rustfmt::skip]
+        let code = [
+            0x48, 0xA1, 0x00, 0x10, 0x00, 0x40, 0x01, 0x00, 0x00, 0x00, // mov rax, [0x140001000]
+            0x48, 0xA1, 0x00, 0x10, 0x00, 0x40, 0x02, 0x00, 0x00, 0x00, // mov rax, [0x240001000]
+        ];
+
+        let references = find_references_in_bytes(&code, 0x140000000, 0x140001000);
+
+        assert_eq!(references, vec![0x140000002]);
+    }
+
+    #[test]
+    fn find_wide_strings_in_bytes_reports_aligned_runs() {
+        let mut bytes = vec![0x41, 0xFF, 0xFF];
+        bytes.extend("Zo\u{EB} 1".encode_utf16().flat_map(u16::to_le_bytes));
+        bytes.extend([0x00, 0x00]);
+        bytes.extend("ab".encode_utf16().flat_map(u16::to_le_bytes));
+
+        // The leading byte is at an odd address, so the code units start one byte in.
+        let strings = find_wide_strings_in_bytes(&bytes, 0x2001, 3);
+
+        assert_eq!(strings, vec![(0x2004, "Zo\u{EB} 1".to_string())]);
+    }
+
+    #[test]
+    fn find_wide_strings_in_bytes_at_even_base() {
+        let mut bytes = vec![0x00, 0xD8]; // unpaired high surrogate
+        bytes.extend("Name".encode_utf16().flat_map(u16::to_le_bytes));
+
+        assert_eq!(
+            find_wide_strings_in_bytes(&bytes, 0x1000, 4),
+            vec![(0x1002, "Name".to_string())]
+        );
+        assert!(find_wide_strings_in_bytes(&bytes, 0x1000, 5).is_empty());
+    }
+
+    /// Builds a PE32 file whose `.text` maps 0x100 of its 0x200 raw bytes at 0x400 to RVA 0x1000.
+    fn module_file() -> Vec<u8> {
+        let mut file = vec![0u8; 0x600];
+        file[0x0..0x2].copy_from_slice(b"MZ");
+        file[0x3C..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        file[0x80..0x84].copy_from_slice(b"PE\0\0");
+        file[0x86..0x88].copy_from_slice(&1u16.to_le_bytes());
+        file[0x94..0x96].copy_from_slice(&0xE0u16.to_le_bytes());
+        file[0x98..0x9A].copy_from_slice(&0x10Bu16.to_le_bytes());
+
+        let section = 0x80 + 0x18 + 0xE0;
+        file[section..section + 5].copy_from_slice(b".text");
+        file[section + 8..section + 12].copy_from_slice(&0x100u32.to_le_bytes());
+        file[section + 12..section + 16].copy_from_slice(&0x1000u32.to_le_bytes());
+        file[section + 16..section + 20].copy_from_slice(&0x200u32.to_le_bytes());
+        file[section + 20..section + 24].copy_from_slice(&0x400u32.to_le_bytes());
+
+        for (i, byte) in file[0x400..].iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+
+        file
+    }
+
+    #[test]
+    fn diff_of_identical_module_text_is_empty() {
+        let file = module_file();
+        let (rva, file_text) = file_text_section(&file).unwrap();
+
+        assert_eq!(rva, 0x1000);
+        assert_eq!(file_text, &file[0x400..0x500]);
+        assert!(diff_bytes(file_text, file_text, rva).is_empty());
+    }
+
+    #[test]
+    fn diff_of_patched_module_text_reports_each_byte() {
+        let file = module_file();
+        let (rva, file_text) = file_text_section(&file).unwrap();
+
+        let mut memory_text = file_text.to_vec();
+        memory_text[0x10] = 0xCC;
+        memory_text[0xFF] = 0x90;
+
+        assert_eq!(
+            diff_bytes(&memory_text, file_text, rva),
+            vec![(0x1010, 0xCC, 0x10), (0x10FF, 0x90, 0xFF)]
+        );
+        // Only the overlapping length is compared
+        assert!(diff_bytes(&memory_text[..0x10], file_text, rva).is_empty());
+    }
+
+    #[test]
+    fn file_text_section_rejects_truncated_files() {
+        let mut file = module_file();
+        file.truncate(0x480);
+
+        assert!(file_text_section(&file).is_err());
+    }
+
+    #[test]
+    fn find_detours_in_bytes_reports_jumps_out_of_module() {
+        #[rustfmt::skip]
+        let code = [
+            0xE9, 0xFB, 0xFF, 0xFF, 0x4F, // jmp 0x50401000, above the module
+            0xCC, 0xCC,
+            0xE8, 0xF4, 0x00, 0x00, 0x00, // call 0x401100, inside of the module
+            0xC3,
+            0xE9, 0xEE, 0xEF, 0xEF, 0xFF, // jmp 0x300000, below the module
+            0x90,
+            0x8B, 0xE9, 0x00, 0x00, 0x00, 0x80, // mov ebp, ecx; not at a prologue
+        ];
+
+        let detours = find_detours_in_bytes(&code, 0x401000, 0x400000..0x500000);
+
+        assert_eq!(detours, vec![(0x401000, 0x50401000), (0x40100D, 0x300000)]);
+    }
+
+    #[cfg(feature = "disassembly")]
+    #[test]
+    fn signature_from_bytes_wildcards_addresses() {
+        #[rustfmt::skip]
+        let code = [
+            0x68, 0x34, 0x12, 0x40, 0x00, // push 0x401234
+            0xB8, 0x10, 0x00, 0x00, 0x00, // mov eax, 0x10
+            0x8B, 0x41, 0x08, // mov eax, [ecx + 8]
+            0xFF, 0x24, 0x85, 0x00, 0x10, 0x40, 0x00, // jmp [eax*4 + 0x401000]
+            0xE8, 0x00, 0x01, 0x00, 0x00, // call rel32
+        ];
+
+        let signature = signature_from_bytes(&code, 0x401000, 32, Some(0x400000..0x500000)).unwrap();
+
+        #[rustfmt::skip]
+        let expected = [
+            Some(0x68), None, None, None, None,
+            Some(0xB8), Some(0x10), Some(0x00), Some(0x00), Some(0x00),
+            Some(0x8B), Some(0x41), Some(0x08),
+            Some(0xFF), Some(0x24), Some(0x85), None, None, None, None,
+            Some(0xE8), None, None, None, None,
+        ];
+        assert_eq!(signature, expected);
+    }
+
+    #[cfg(feature = "disassembly")]
+    #[test]
+    fn signature_from_bytes_keeps_immediates_outside_image() {
+        let code = [0x68, 0x34, 0x12, 0x40, 0x00]; // push 0x401234
+
+        let signature = signature_from_bytes(&code, 0x10000000, 32, None).unwrap();
+
+        assert_eq!(signature, code.map(Some));
+    }
+}
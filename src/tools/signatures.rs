@@ -0,0 +1,364 @@
+#[cfg(feature = "signatures")]
+use std::collections::HashMap;
+#[cfg(feature = "signatures")]
+use std::path::Path;
+use std::time::Duration;
+
+use windows::Win32::Foundation::HMODULE;
+
+use crate::process::module::{module_by_name, read_module_image};
+use crate::process::pattern::{
+    find_pattern_offsets, read_at_signature, resolve_signature, resolve_signature_in_image, Pattern,
+    SignatureAnchor,
+};
+use crate::process::SafeHandle;
+use crate::{memop_err, MemOpResult};
+#[cfg(feature = "signatures")]
+use crate::MemOpError;
+
+/// A signature loaded from a signature database, see `load_signatures()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureDef {
+    /// File name of the module to scan, e.g. `game.exe`
+    pub module: String,
+    /// IDA byte pattern
+    pub pattern: String,
+    /// How the address is derived from the match
+    pub anchor: SignatureAnchor,
+}
+
+impl SignatureDef {
+    /// Scans the signature's module and resolves its anchor, see `resolve_signature()`.
+    pub fn resolve(&self, handle: &SafeHandle) -> MemOpResult<usize> {
+        let module = module_by_name(handle, &self.module, true, None)?
+            .ok_or(memop_err!("No module named \"{}\".", self.module))?;
+
+        resolve_signature(handle, module, &self.pattern, self.anchor)
+    }
+
+    /// Resolves the signature against an already read image of its module.
+    pub fn resolve_in_image(&self, image: &[u8], base: usize) -> MemOpResult<usize> {
+        resolve_signature_in_image(image, base, &self.pattern, self.anchor)
+    }
+
+    /// Reads a value at the address the signature resolves to, see `read_at_signature()`.
+    pub fn read<T: bytemuck::Pod>(
+        &self,
+        handle: &SafeHandle,
+        timeout: Option<Duration>,
+    ) -> MemOpResult<T> {
+        let module = module_by_name(handle, &self.module, true, None)?
+            .ok_or(memop_err!("No module named \"{}\".", self.module))?;
+
+        read_at_signature::<T>(handle, module, &self.pattern, self.anchor, timeout)
+    }
+}
+
+/// Loads a signature database, mapping each signature's name to its definition.
+///
+/// Files ending in `.json` are parsed as JSON, anything else as TOML. Either way, the database holds
+/// one table (object) per signature, keyed by its name. The keys of a signature are:
+///
+/// * `module` and `pattern` are required strings
+/// * `offset` (default `0`) is added to the match, or locates the operand with `rel_resolve`/`absolute`
+/// * `rel_resolve = true` reads a rel32 displacement at `offset`, relative to the end of the
+///   `instr_len`-byte instruction (required)
+/// * `absolute = true` reads an absolute 32-bit address at `offset`
+///
+/// Requires the `signatures` feature.
+///
+/// # Example
+/// ```toml
+/// # Signatures for game.exe 1.2
+/// [player_health]
+/// module = "game.exe"
+/// pattern = "8B 81 ?? ?? ?? ?? 85 C0"
+/// offset = 2
+///
+/// [update_player]
+/// module = "game.exe"
+/// pattern = "E8 ?? ?? ?? ?? 83 C4 08"
+/// offset = 1
+/// rel_resolve = true
+/// instr_len = 5
+/// ```
+#[cfg(feature = "signatures")]
+pub fn load_signatures(path: impl AsRef<Path>) -> MemOpResult<HashMap<String, SignatureDef>> {
+    let path = path.as_ref();
+    let source = std::fs::read_to_string(path).map_err(MemOpError::new)?;
+
+    match path.extension().is_some_and(|e| e.eq_ignore_ascii_case("json")) {
+        true => parse_signatures_json(&source),
+        false => parse_signatures(&source),
+    }
+}
+
+/// Parses a TOML signature database, see `load_signatures()` for its keys.
+#[cfg(feature = "signatures")]
+pub fn parse_signatures(source: &str) -> MemOpResult<HashMap<String, SignatureDef>> {
+    let tables: HashMap<String, SignatureTable> =
+        toml::from_str(source).map_err(MemOpError::new)?;
+
+    signature_defs(tables)
+}
+
+/// Parses a JSON signature database, see `load_signatures()` for its keys.
+#[cfg(feature = "signatures")]
+pub fn parse_signatures_json(source: &str) -> MemOpResult<HashMap<String, SignatureDef>> {
+    let tables: HashMap<String, SignatureTable> =
+        serde_json::from_str(source).map_err(MemOpError::new)?;
+
+    signature_defs(tables)
+}
+
+/// How distinctive a signature is within a module, see `assess_signature()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignatureQuality {
+    /// Amount of matches of the whole pattern
+    pub match_count: usize,
+    /// Length of the shortest prefix of the pattern matching exactly once, `None` if the whole pattern doesn't
+    pub unique_prefix_len: Option<usize>,
+}
+
+/// Assesses how unique a pattern is within a module, to trim new signatures to their distinctive part.
+///
+/// # Example
+/// ```rust,norun
+/// let quality = assess_signature(&handle, module, "55 8B EC 83 EC 10 A1 ?? ?? ?? ?? 33 C5")?;
+/// if let Some(len) = quality.unique_prefix_len {
+///     println!("Only the first {len} bytes are needed.");
+/// }
+/// ```
+pub fn assess_signature(
+    handle: &SafeHandle,
+    module: HMODULE,
+    pattern: impl Into<Pattern>,
+) -> MemOpResult<SignatureQuality> {
+    let image = read_module_image(handle, module)?;
+
+    Ok(assess_signature_in_bytes(&image, pattern))
+}
+
+/// Assesses how unique a pattern is within a buffer, see `assess_signature()`.
+pub fn assess_signature_in_bytes(bytes: &[u8], pattern: impl Into<Pattern>) -> SignatureQuality {
+    let pattern = pattern.into();
+    let count = |len: usize| find_pattern_offsets(bytes, &pattern.bytes()[..len]).len();
+
+    let match_count = count(pattern.len());
+    if match_count != 1 || pattern.is_empty() {
+        return SignatureQuality {
+            match_count,
+            unique_prefix_len: None,
+        };
+    }
+
+    // Lengthening a prefix never adds matches, so the shortest unique one can be searched for.
+    let (mut low, mut high) = (1, pattern.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match count(mid) > 1 {
+            true => low = mid + 1,
+            false => high = mid,
+        }
+    }
+
+    SignatureQuality {
+        match_count,
+        unique_prefix_len: Some(low),
+    }
+}
+
+/// A signature as written in a signature database, see `load_signatures()`.
+#[cfg(feature = "signatures")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SignatureTable {
+    module: String,
+    pattern: String,
+    #[serde(default)]
+    offset: i64,
+    #[serde(default)]
+    rel_resolve: bool,
+    #[serde(default)]
+    absolute: bool,
+    instr_len: Option<i64>,
+}
+
+/// Builds a `SignatureDef` from every table, naming the signature on errors.
+#[cfg(feature = "signatures")]
+fn signature_defs(
+    tables: HashMap<String, SignatureTable>,
+) -> MemOpResult<HashMap<String, SignatureDef>> {
+    tables
+        .into_iter()
+        .map(|(name, table)| {
+            let def = signature_def(table).map_err(|e| memop_err!("Signature \"{name}\": {e}"))?;
+            Ok((name, def))
+        })
+        .collect()
+}
+
+/// Builds a `SignatureDef` from the keys of its table.
+#[cfg(feature = "signatures")]
+fn signature_def(table: SignatureTable) -> MemOpResult<SignatureDef> {
+    let unsigned = |key: &str, value: i64| {
+        usize::try_from(value).map_err(|_| memop_err!("\"{key}\" must not be negative."))
+    };
+
+    let anchor = match (table.rel_resolve, table.absolute) {
+        (true, true) => return Err(memop_err!("\"rel_resolve\" and \"absolute\" are exclusive.")),
+        (true, false) => {
+            let instr_len = table
+                .instr_len
+                .ok_or(memop_err!("\"rel_resolve\" requires \"instr_len\"."))?;
+
+            SignatureAnchor::Relative {
+                disp_offset: unsigned("offset", table.offset)?,
+                instr_len: unsigned("instr_len", instr_len)?,
+            }
+        }
+        (false, true) => SignatureAnchor::Absolute32(unsigned("offset", table.offset)?),
+        (false, false) => SignatureAnchor::Offset(
+            isize::try_from(table.offset)
+                .map_err(|_| memop_err!("\"offset\" does not fit in an isize."))?,
+        ),
+    };
+
+    Ok(SignatureDef {
+        module: table.module,
+        pattern: table.pattern,
+        anchor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "signatures")]
+    const DATABASE: &str = r#"
+# Signatures for game.exe 1.2
+[player_health]
+module = "game.exe"
+pattern = "8B 81 ?? ?? ?? ?? 85 C0" # the health load
+offset = 2
+
+[update_player]
+module = "game.exe"
+pattern = "E8 ?? ?? ?? ?? 83 C4 08"
+offset = 0x1
+rel_resolve = true
+instr_len = 5
+
+[player_count]
+module = "game.exe"
+pattern = "A1 ?? ?? ?? ?? 85 C0"
+offset = 1
+absolute = true
+"#;
+
+    #[test]
+    fn assess_signature_in_bytes_finds_shortest_unique_prefix() {
+        let bytes = [0x55, 0x8B, 0xEC, 0x55, 0x8B, 0xEC, 0x83, 0xEC, 0x10, 0x90];
+
+        assert_eq!(
+            assess_signature_in_bytes(&bytes, "55 8B EC 83 EC 10"),
+            SignatureQuality {
+                match_count: 1,
+                unique_prefix_len: Some(4),
+            }
+        );
+        assert_eq!(
+            assess_signature_in_bytes(&bytes, "55 ?? EC 83"),
+            SignatureQuality {
+                match_count: 1,
+                unique_prefix_len: Some(4),
+            }
+        );
+    }
+
+    #[test]
+    fn assess_signature_in_bytes_without_a_single_match() {
+        let bytes = [0x55, 0x8B, 0xEC, 0x55, 0x8B, 0xEC, 0x90];
+
+        assert_eq!(
+            assess_signature_in_bytes(&bytes, "55 8B EC"),
+            SignatureQuality {
+                match_count: 2,
+                unique_prefix_len: None,
+            }
+        );
+        assert_eq!(assess_signature_in_bytes(&bytes, "CC").match_count, 0);
+    }
+
+    #[cfg(feature = "signatures")]
+    #[test]
+    fn parse_signatures_reads_every_table() {
+        let signatures = parse_signatures(DATABASE).unwrap();
+
+        assert_eq!(signatures.len(), 3);
+        assert_eq!(
+            signatures["player_health"],
+            SignatureDef {
+                module: "game.exe".to_string(),
+                pattern: "8B 81 ?? ?? ?? ?? 85 C0".to_string(),
+                anchor: SignatureAnchor::Offset(2),
+            }
+        );
+        assert_eq!(
+            signatures["update_player"].anchor,
+            SignatureAnchor::Relative {
+                disp_offset: 1,
+                instr_len: 5,
+            }
+        );
+        assert_eq!(signatures["player_count"].anchor, SignatureAnchor::Absolute32(1));
+    }
+
+    #[cfg(feature = "signatures")]
+    #[test]
+    fn parse_signatures_json_matches_toml() {
+        let source = r#"{
+            "player_health": {
+                "module": "game.exe",
+                "pattern": "8B 81 ?? ?? ?? ?? 85 C0",
+                "offset": 2
+            }
+        }"#;
+
+        let signatures = parse_signatures_json(source).unwrap();
+
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(signatures["player_health"], parse_signatures(DATABASE).unwrap()["player_health"]);
+    }
+
+    #[cfg(feature = "signatures")]
+    #[test]
+    fn parse_signatures_rejects_invalid_tables() {
+        let header = "[sig]\nmodule = \"a.exe\"\npattern = \"E8\"\n";
+
+        assert!(parse_signatures(&format!("{header}rel_resolve = true")).is_err());
+        assert!(parse_signatures(&format!("{header}rel_resolve = true\nabsolute = true")).is_err());
+        assert!(parse_signatures(&format!("{header}absolute = true\noffset = -1")).is_err());
+        assert!(parse_signatures(&format!("{header}offest = 1")).is_err());
+        assert!(parse_signatures("[sig]\nmodule = \"a.exe\"").is_err());
+    }
+
+    #[cfg(feature = "signatures")]
+    #[test]
+    fn loaded_signatures_resolve_against_fake_module() {
+        #[rustfmt::skip]
+        let image = [
+            0x90, 0x90, 0x90, 0x90,
+            0x8B, 0x81, 0x40, 0x01, 0x00, 0x00, 0x85, 0xC0, // mov eax, [ecx + 0x140]; test eax, eax
+            0xE8, 0x20, 0x00, 0x00, 0x00, 0x83, 0xC4, 0x08, // call +0x20; add esp, 8
+            0xA1, 0x78, 0x56, 0x34, 0x12, 0x85, 0xC0, // mov eax, [0x12345678]; test eax, eax
+        ];
+        let signatures = parse_signatures(DATABASE).unwrap();
+        let resolve = |name: &str| signatures[name].resolve_in_image(&image, 0x400000).unwrap();
+
+        assert_eq!(resolve("player_health"), 0x400006);
+        assert_eq!(resolve("update_player"), 0x400031);
+        assert_eq!(resolve("player_count"), 0x12345678);
+    }
+}
@@ -0,0 +1,15 @@
+# Source of symbols.pdb, used by the process::symbols tests. Regenerate with:
+#   llvm-mc -triple x86_64-pc-windows-msvc -filetype=obj symbols.s -o symbols.obj
+#   lld-link /dll /noentry /debug /pdb:symbols.pdb /out:symbols.dll symbols.obj \
+#       /export:zhol_fixture_function /export:zhol_fixture_data
+# The function lands at RVA 0x1000 and the data at RVA 0x3000.
+    .text
+    .globl  zhol_fixture_function
+zhol_fixture_function:
+    movl    $42, %eax
+    retq
+
+    .data
+    .globl  zhol_fixture_data
+zhol_fixture_data:
+    .long   0x11223344